@@ -4,17 +4,62 @@ use std::env;
 use std::path::Path;
 use std::process::Command;
 
+/// Check that a version looks like `1.28.1` or `1.28.1-rc0`, so it's safe to use in the download url.
+fn is_valid_version(version: &str) -> bool {
+    let mut parts = version.splitn(2, '-');
+    let release = parts.next().unwrap_or("");
+    let numbers_valid = release.split('.').count() == 3 &&
+        release.split('.').all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    let pre_release_valid = match parts.next() {
+        Some(pre_release) => !pre_release.is_empty() && pre_release.chars().all(|c| c.is_ascii_alphanumeric() || c == '.'),
+        None => true
+    };
+    numbers_valid && pre_release_valid
+}
+
 fn main() {
-    let mongoc_version = env!("CARGO_PKG_VERSION")
-        .split('-')
-        .next()
-        .expect("Crate version is not valid");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=MONGOC_VERSION");
+    println!("cargo:rerun-if-env-changed=MONGOC_SYSTEM");
+
+    // Link a compatible system libmongoc only when asked to. Picking up whatever libmongoc
+    // happens to be installed would silently ignore the features below, which change how
+    // the C driver is built. `icu` is left out because it's a default feature and a system
+    // libmongoc comes with its own ICU setting.
+    let use_system = env::var("MONGOC_SYSTEM").map(|value| value == "1").unwrap_or(false);
+    if use_system {
+        assert!(
+            !cfg!(any(feature = "sasl", feature = "zstd", feature = "zlib-compression")),
+            "MONGOC_SYSTEM=1 can't be combined with the sasl, zstd or zlib-compression features"
+        );
+        pkg_config::Config::new()
+            .range_version("1.28.0".."2.0.0")
+            .probe("libmongoc-1.0")
+            .expect("Cannot find a compatible system libmongoc");
+        return;
+    }
+
+    // The version of the C driver can be overridden to try another release, the
+    // bindings are written for the version of this crate.
+    let mongoc_version = match env::var("MONGOC_VERSION") {
+        Ok(version) => {
+            assert!(is_valid_version(&version), "MONGOC_VERSION is not a valid version: {}", version);
+            version
+        },
+        Err(_) => env!("CARGO_PKG_VERSION")
+            .split('-')
+            .next()
+            .expect("Crate version is not valid")
+            .to_string()
+    };
 
     let out_dir_var = env::var("OUT_DIR").expect("No out dir");
     let out_dir = Path::new(&out_dir_var);
     let driver_src_path = out_dir.join(format!("mongo-c-driver-{}", mongoc_version));
+    // Install every version in its own directory, so changing the version builds it again
+    let install_path = out_dir.join(format!("mongo-c-driver-{}-install", mongoc_version));
 
-    let libmongoc_path = out_dir.join("usr/local/lib/libmongoc-static-1.0.a");
+    let libmongoc_path = install_path.join("usr/local/lib/libmongoc-static-1.0.a");
     if !libmongoc_path.exists() {
         // Download and extract driver archive
         let url = format!(
@@ -48,8 +93,17 @@ fn main() {
         cmake.current_dir(&driver_src_path);
 
         let pkg = pkg_config::Config::new();
-        pkg.probe("zlib").expect("Cannot find zlib");
-        #[cfg(target_os = "linux")] pkg.probe("icu-i18n").expect("Cannot find icu");
+        if cfg!(feature = "zlib-compression") {
+            // Compiled into libmongoc, so there's no need for a system zlib
+            cmake.arg("-DENABLE_ZLIB=BUNDLED");
+        } else {
+            pkg.probe("zlib").expect("Cannot find zlib");
+        }
+        if cfg!(feature = "icu") {
+            #[cfg(target_os = "linux")] pkg.probe("icu-i18n").expect("Cannot find icu");
+        } else {
+            cmake.arg("-DENABLE_ICU=OFF");
+        }
         match pkg.probe("snappy") {
             Ok(_) => {
                 cmake.arg("-DENABLE_SNAPPY=ON");
@@ -59,19 +113,36 @@ fn main() {
                 cmake.arg("-DENABLE_SNAPPY=OFF");
             }
         }
-        match pkg.probe("zstd") {
-            Ok(_) => {
-                cmake.arg("-DENABLE_ZSTD=ON");
-            },
-            Err(e) => {
-                println!("Zstd not found: {}", e);
-                cmake.arg("-DENABLE_ZSTD=OFF");
+        if cfg!(feature = "zstd") {
+            pkg.probe("libzstd").expect("Cannot find zstd, install it to use the zstd feature");
+            cmake.arg("-DENABLE_ZSTD=ON");
+        } else {
+            match pkg.probe("libzstd") {
+                Ok(_) => {
+                    cmake.arg("-DENABLE_ZSTD=ON");
+                },
+                Err(e) => {
+                    println!("Zstd not found: {}", e);
+                    cmake.arg("-DENABLE_ZSTD=OFF");
+                }
             }
         }
 
         cmake.arg("-DENABLE_AUTOMATIC_INIT_AND_CLEANUP=OFF");
         cmake.arg("-DENABLE_SSL=OPENSSL");
-        cmake.arg("-DENABLE_SASL=OFF");
+        // Fail the build instead of silently dropping mongodb+srv:// support if
+        // no resolver is available.
+        cmake.arg("-DENABLE_SRV=ON");
+        if cfg!(feature = "sasl") {
+            if cfg!(target_os = "windows") {
+                cmake.arg("-DENABLE_SASL=SSPI");
+            } else {
+                pkg.probe("libsasl2").expect("Cannot find libsasl2, install Cyrus SASL to use the sasl feature");
+                cmake.arg("-DENABLE_SASL=CYRUS");
+            }
+        } else {
+            cmake.arg("-DENABLE_SASL=OFF");
+        }
         cmake.arg("-DENABLE_STATIC=ON");
         cmake.arg("-DENABLE_BSON=ON");
         cmake.arg("-DENABLE_ENABLE_EXAMPLES=OFF");
@@ -88,7 +159,7 @@ fn main() {
         // Set up make install command
         let mut make = Command::new("make");
         make.current_dir(&driver_src_path);
-        make.arg(format!("DESTDIR={}", out_dir.to_string_lossy()));
+        make.arg(format!("DESTDIR={}", install_path.to_string_lossy()));
         make.arg("install");
 
         // Run make command
@@ -96,8 +167,20 @@ fn main() {
     }
 
     // Output to Cargo
-    println!("cargo:rustc-link-search=native={}/usr/local/lib", &out_dir.to_string_lossy());
+    println!("cargo:rustc-link-search=native={}/usr/local/lib", &install_path.to_string_lossy());
     println!("cargo:rustc-link-lib=static=bson-static-1.0");
     println!("cargo:rustc-link-lib=static=mongoc-static-1.0");
-    println!("cargo:rustc-link-lib=resolv");
+    if cfg!(target_os = "windows") {
+        println!("cargo:rustc-link-lib=dnsapi");
+    } else {
+        println!("cargo:rustc-link-lib=resolv");
+    }
+    if cfg!(feature = "sasl") {
+        if cfg!(target_os = "windows") {
+            println!("cargo:rustc-link-lib=secur32");
+            println!("cargo:rustc-link-lib=crypt32");
+        } else {
+            println!("cargo:rustc-link-lib=sasl2");
+        }
+    }
 }
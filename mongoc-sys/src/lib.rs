@@ -14,6 +14,8 @@ pub mod bindings {
     pub type uint16_t = ::libc::c_ushort;
     pub type uint32_t = ::libc::c_uint;
     pub type size_t = ::libc::c_ulong;
+    pub type ssize_t = ::libc::c_long;
+    pub type uint64_t = ::libc::c_ulonglong;
 
     // Libbson
     #[repr(C)]
@@ -26,6 +28,21 @@ pub mod bindings {
     impl Clone for bson_t {
         fn clone(&self) -> Self { *self }
     }
+    pub const BSON_TYPE_OID: ::libc::c_uint = 7;
+    #[repr(C)]
+    #[derive(Copy)]
+    pub struct bson_value_t {
+        pub value_type: ::libc::c_uint,
+        pub padding: int32_t,
+        pub value: [uint64_t; 3usize],
+    }
+    impl Clone for bson_value_t {
+        fn clone(&self) -> Self { *self }
+    }
+    #[repr(C, align(128))]
+    pub struct bson_iter_t {
+        pub padding: [uint8_t; 128usize],
+    }
     #[repr(C)]
     pub struct bson_reader_t {
         pub _type: uint32_t,
@@ -38,12 +55,16 @@ pub mod bindings {
     }
     extern "C" {
         pub fn bson_as_json(bson: *const bson_t, length: *mut size_t) -> *mut ::libc::c_char;
+        pub fn bson_copy(bson: *const bson_t) -> *mut bson_t;
         pub fn bson_destroy(bson: *mut bson_t) -> ();
         pub fn bson_free(mem: *mut ::libc::c_void) -> ();
+        pub fn bson_strfreev(strv: *mut *mut ::libc::c_char) -> ();
         pub fn bson_get_data(bson: *const bson_t) -> *const uint8_t;
         pub fn bson_new() -> *mut bson_t;
         pub fn bson_new_from_data(data: *const uint8_t, length: size_t) -> *mut bson_t;
         pub fn bson_reader_new_from_data(data: *const uint8_t, length: size_t) -> *mut bson_reader_t;
+        pub fn bson_iter_init_find(iter: *mut bson_iter_t, bson: *const bson_t, key: *const ::libc::c_char) -> u8;
+        pub fn bson_iter_value(iter: *mut bson_iter_t) -> *const bson_value_t;
     }
 
     // Init and logging
@@ -74,7 +95,14 @@ pub mod bindings {
     pub const MONGOC_READ_NEAREST: ::libc::c_uint = 10;
     extern "C" {
         pub fn mongoc_read_prefs_new(read_mode: mongoc_read_mode_t) -> *mut mongoc_read_prefs_t;
+        pub fn mongoc_read_prefs_copy(read_prefs: *const mongoc_read_prefs_t) -> *mut mongoc_read_prefs_t;
         pub fn mongoc_read_prefs_destroy(read_prefs: *mut mongoc_read_prefs_t) -> ();
+        pub fn mongoc_read_prefs_get_mode(read_prefs: *const mongoc_read_prefs_t) -> mongoc_read_mode_t;
+        pub fn mongoc_read_prefs_is_valid(read_prefs: *const mongoc_read_prefs_t) -> u8;
+        pub fn mongoc_read_prefs_get_tags(read_prefs: *const mongoc_read_prefs_t) -> *const bson_t;
+        pub fn mongoc_read_prefs_set_hedge(read_prefs: *mut mongoc_read_prefs_t, hedge: *const bson_t) -> ();
+        pub fn mongoc_read_prefs_get_max_staleness_seconds(read_prefs: *const mongoc_read_prefs_t) -> int64_t;
+        pub fn mongoc_read_prefs_set_max_staleness_seconds(read_prefs: *mut mongoc_read_prefs_t, max_staleness_seconds: int64_t) -> ();
     }
 
     // Uri
@@ -83,25 +111,78 @@ pub mod bindings {
         pub fn mongoc_uri_new(uri_string: *const ::libc::c_char) -> *mut mongoc_uri_t;
         pub fn mongoc_uri_get_string(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
         pub fn mongoc_uri_get_database(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_username(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_password(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_auth_source(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_auth_mechanism(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_appname(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_srv_hostname(uri: *const mongoc_uri_t) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_hosts(uri: *const mongoc_uri_t) -> *const mongoc_host_list_t;
+        pub fn mongoc_uri_get_tls(uri: *const mongoc_uri_t) -> u8;
+        pub fn mongoc_uri_has_option(uri: *const mongoc_uri_t, key: *const ::libc::c_char) -> u8;
+        pub fn mongoc_uri_get_option_as_bool(uri: *const mongoc_uri_t, option: *const ::libc::c_char, fallback: u8) -> u8;
+        pub fn mongoc_uri_get_option_as_utf8(uri: *const mongoc_uri_t, option: *const ::libc::c_char, fallback: *const ::libc::c_char) -> *const ::libc::c_char;
+        pub fn mongoc_uri_get_option_as_int32(uri: *const mongoc_uri_t, option: *const ::libc::c_char, fallback: int32_t) -> int32_t;
+        pub fn mongoc_uri_set_option_as_bool(uri: *mut mongoc_uri_t, option: *const ::libc::c_char, value: u8) -> u8;
+        pub fn mongoc_uri_set_option_as_int32(uri: *mut mongoc_uri_t, option: *const ::libc::c_char, value: int32_t) -> u8;
+        pub fn mongoc_uri_set_option_as_utf8(uri: *mut mongoc_uri_t, option: *const ::libc::c_char, value: *const ::libc::c_char) -> u8;
+        pub fn mongoc_uri_copy(uri: *const mongoc_uri_t) -> *mut mongoc_uri_t;
         pub fn mongoc_uri_destroy(uri: *mut mongoc_uri_t) -> ();
     }
 
+    // Read concern
+    pub enum mongoc_read_concern_t {}
+    extern "C" {
+        pub fn mongoc_read_concern_new() -> *mut mongoc_read_concern_t;
+        pub fn mongoc_read_concern_copy(read_concern: *const mongoc_read_concern_t) -> *mut mongoc_read_concern_t;
+        pub fn mongoc_read_concern_destroy(read_concern: *mut mongoc_read_concern_t) -> ();
+        pub fn mongoc_read_concern_get_level(read_concern: *const mongoc_read_concern_t) -> *const ::libc::c_char;
+        pub fn mongoc_read_concern_set_level(read_concern: *mut mongoc_read_concern_t, level: *const ::libc::c_char) -> u8;
+        pub fn mongoc_read_concern_append(read_concern: *mut mongoc_read_concern_t, command: *mut bson_t) -> u8;
+    }
+
     // Write concern
     pub enum mongoc_write_concern_t {}
+    pub const MONGOC_WRITE_CONCERN_W_UNACKNOWLEDGED: int32_t = 0;
+    pub const MONGOC_WRITE_CONCERN_W_DEFAULT: int32_t = -2;
+    pub const MONGOC_WRITE_CONCERN_W_MAJORITY: int32_t = -3;
     extern "C" {
         pub fn mongoc_write_concern_new() -> *mut mongoc_write_concern_t;
+        pub fn mongoc_write_concern_copy(write_concern: *const mongoc_write_concern_t) -> *mut mongoc_write_concern_t;
         pub fn mongoc_write_concern_destroy(write_concern: *mut mongoc_write_concern_t) -> ();
+        pub fn mongoc_write_concern_get_w(write_concern: *const mongoc_write_concern_t) -> int32_t;
+        pub fn mongoc_write_concern_get_wmajority(write_concern: *const mongoc_write_concern_t) -> u8;
+        pub fn mongoc_write_concern_set_w(write_concern: *mut mongoc_write_concern_t, w: int32_t) -> ();
+        pub fn mongoc_write_concern_set_wmajority(write_concern: *mut mongoc_write_concern_t, wtimeout_msec: int32_t) -> ();
+        pub fn mongoc_write_concern_get_wtimeout_int64(write_concern: *const mongoc_write_concern_t) -> int64_t;
+        pub fn mongoc_write_concern_set_wtimeout_int64(write_concern: *mut mongoc_write_concern_t, wtimeout_msec: int64_t) -> ();
+        pub fn mongoc_write_concern_get_journal(write_concern: *const mongoc_write_concern_t) -> u8;
+        pub fn mongoc_write_concern_set_journal(write_concern: *mut mongoc_write_concern_t, journal: u8) -> ();
+        pub fn mongoc_write_concern_journal_is_set(write_concern: *const mongoc_write_concern_t) -> u8;
+        pub fn mongoc_write_concern_append(write_concern: *mut mongoc_write_concern_t, command: *mut bson_t) -> u8;
+        pub fn mongoc_write_concern_is_default(write_concern: *const mongoc_write_concern_t) -> u8;
     }
 
     // Database
     pub enum mongoc_database_t {}
     extern "C" {
         pub fn mongoc_database_command(database: *mut mongoc_database_t, flags: mongoc_query_flags_t, skip: uint32_t, limit: uint32_t, batch_size: uint32_t, command: *const bson_t, fields: *const bson_t, read_prefs: *const mongoc_read_prefs_t) -> *mut mongoc_cursor_t;
+        pub fn mongoc_database_watch(database: *const mongoc_database_t, pipeline: *const bson_t, opts: *const bson_t) -> *mut mongoc_change_stream_t;
+        pub fn mongoc_database_get_collection_names_with_opts(database: *mut mongoc_database_t, opts: *const bson_t, error: *mut bson_error_t) -> *mut *mut ::libc::c_char;
         pub fn mongoc_database_command_simple(database: *mut mongoc_database_t, command: *const bson_t, read_prefs: *const mongoc_read_prefs_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_database_create_collection(database: *mut mongoc_database_t, name: *const ::libc::c_char, options: *const bson_t, error: *mut bson_error_t) -> *mut mongoc_collection_t;
         pub fn mongoc_database_get_collection(database: *mut mongoc_database_t, name: *const ::libc::c_char) -> *mut mongoc_collection_t;
         pub fn mongoc_database_get_name(database: *mut mongoc_database_t) -> *const ::libc::c_char;
+        pub fn mongoc_database_drop(database: *mut mongoc_database_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_database_add_user(database: *mut mongoc_database_t, username: *const ::libc::c_char, password: *const ::libc::c_char, roles: *const bson_t, custom_data: *const bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_database_remove_user(database: *mut mongoc_database_t, username: *const ::libc::c_char, error: *mut bson_error_t) -> u8;
         pub fn mongoc_database_destroy(database: *mut mongoc_database_t) -> ();
+        pub fn mongoc_database_get_write_concern(database: *const mongoc_database_t) -> *const mongoc_write_concern_t;
+        pub fn mongoc_database_set_write_concern(database: *mut mongoc_database_t, write_concern: *const mongoc_write_concern_t) -> ();
+        pub fn mongoc_database_get_read_concern(database: *const mongoc_database_t) -> *const mongoc_read_concern_t;
+        pub fn mongoc_database_set_read_concern(database: *mut mongoc_database_t, read_concern: *const mongoc_read_concern_t) -> ();
+        pub fn mongoc_database_get_read_prefs(database: *const mongoc_database_t) -> *const mongoc_read_prefs_t;
+        pub fn mongoc_database_set_read_prefs(database: *mut mongoc_database_t, read_prefs: *const mongoc_read_prefs_t) -> ();
         pub fn mongoc_database_has_collection(database: *mut mongoc_database_t, name: *const ::libc::c_char, error: *mut bson_error_t) -> i32;
     }
 
@@ -117,7 +198,9 @@ pub mod bindings {
         pub ca_dir: *const ::libc::c_char,
         pub crl_file: *const ::libc::c_char,
         pub weak_cert_validation: u8,
-        pub padding: [*mut ::libc::c_void; 8usize],
+        pub allow_invalid_hostname: u8,
+        pub internal: *mut ::libc::c_void,
+        pub padding: [*mut ::libc::c_void; 6usize],
     }
     impl Clone for mongoc_ssl_opt_t {
         fn clone(&self) -> Self { *self }
@@ -125,13 +208,105 @@ pub mod bindings {
     extern "C" {
         pub fn mongoc_client_pool_new(uri: *const mongoc_uri_t) -> *mut mongoc_client_pool_t;
         pub fn mongoc_client_pool_set_ssl_opts(pool: *mut mongoc_client_pool_t, opts: *const mongoc_ssl_opt_t) -> ();
+        pub fn mongoc_client_pool_max_size(pool: *mut mongoc_client_pool_t, max_pool_size: uint32_t) -> ();
+        pub fn mongoc_client_pool_min_size(pool: *mut mongoc_client_pool_t, min_pool_size: uint32_t) -> ();
+        pub fn mongoc_client_pool_set_appname(pool: *mut mongoc_client_pool_t, appname: *const ::libc::c_char) -> u8;
         pub fn mongoc_client_pool_pop(pool: *mut mongoc_client_pool_t) -> *mut mongoc_client_t;
+        pub fn mongoc_client_pool_try_pop(pool: *mut mongoc_client_pool_t) -> *mut mongoc_client_t;
         pub fn mongoc_client_pool_push(pool: *mut mongoc_client_pool_t, client: *mut mongoc_client_t) -> ();
         pub fn mongoc_client_pool_destroy(pool: *mut mongoc_client_pool_t) -> ();
         pub fn mongoc_client_get_collection(client: *mut mongoc_client_t, db: *const ::libc::c_char, collection: *const ::libc::c_char) -> *mut mongoc_collection_t;
         pub fn mongoc_client_get_database(client: *mut mongoc_client_t, name: *const ::libc::c_char) -> *mut mongoc_database_t;
+        pub fn mongoc_client_command_simple(client: *mut mongoc_client_t, db_name: *const ::libc::c_char, command: *const bson_t, read_prefs: *const mongoc_read_prefs_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_client_get_server_status(client: *mut mongoc_client_t, read_prefs: *mut mongoc_read_prefs_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_client_watch(client: *mut mongoc_client_t, pipeline: *const bson_t, opts: *const bson_t) -> *mut mongoc_change_stream_t;
+        pub fn mongoc_client_get_database_names_with_opts(client: *mut mongoc_client_t, opts: *const bson_t, error: *mut bson_error_t) -> *mut *mut ::libc::c_char;
         pub fn mongoc_client_read_command_with_opts(client: *mut mongoc_client_t, db: *const ::libc::c_char, command: *const bson_t, read_prefs: *const mongoc_read_prefs_t, opts: *const bson_t, reply: *mut bson_t, error: *mut bson_error_t ) -> u8;
+        pub fn mongoc_client_get_server_descriptions(client: *const mongoc_client_t, n: *mut size_t) -> *mut *mut mongoc_server_description_t;
+        pub fn mongoc_client_select_server(client: *mut mongoc_client_t, for_writes: u8, prefs: *const mongoc_read_prefs_t, error: *mut bson_error_t) -> *mut mongoc_server_description_t;
+    }
+
+    // Client session
+    pub enum mongoc_client_session_t {}
+    pub enum mongoc_session_opt_t {}
+    pub enum mongoc_transaction_opt_t {}
+    extern "C" {
+        pub fn mongoc_client_start_session(client: *mut mongoc_client_t, opts: *const mongoc_session_opt_t, error: *mut bson_error_t) -> *mut mongoc_client_session_t;
+        pub fn mongoc_client_session_append(client_session: *const mongoc_client_session_t, opts: *mut bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_client_session_get_opts(session: *const mongoc_client_session_t) -> *const mongoc_session_opt_t;
+        pub fn mongoc_client_session_get_lsid(session: *const mongoc_client_session_t) -> *const bson_t;
+        pub fn mongoc_client_session_get_cluster_time(session: *const mongoc_client_session_t) -> *const bson_t;
+        pub fn mongoc_client_session_get_operation_time(session: *const mongoc_client_session_t, timestamp: *mut uint32_t, increment: *mut uint32_t) -> ();
+        pub fn mongoc_client_session_start_transaction(session: *mut mongoc_client_session_t, opts: *const mongoc_transaction_opt_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_client_session_in_transaction(session: *const mongoc_client_session_t) -> u8;
+        pub fn mongoc_client_session_commit_transaction(session: *mut mongoc_client_session_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_client_session_abort_transaction(session: *mut mongoc_client_session_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_client_session_destroy(session: *mut mongoc_client_session_t) -> ();
+        pub fn mongoc_session_opts_new() -> *mut mongoc_session_opt_t;
+        pub fn mongoc_session_opts_set_causal_consistency(opts: *mut mongoc_session_opt_t, causal_consistency: u8) -> ();
+        pub fn mongoc_session_opts_get_causal_consistency(opts: *const mongoc_session_opt_t) -> u8;
+        pub fn mongoc_session_opts_destroy(opts: *mut mongoc_session_opt_t) -> ();
+        pub fn mongoc_transaction_opts_new() -> *mut mongoc_transaction_opt_t;
+        pub fn mongoc_transaction_opts_set_read_concern(opts: *mut mongoc_transaction_opt_t, read_concern: *const mongoc_read_concern_t) -> ();
+        pub fn mongoc_transaction_opts_set_write_concern(opts: *mut mongoc_transaction_opt_t, write_concern: *const mongoc_write_concern_t) -> ();
+        pub fn mongoc_transaction_opts_set_read_prefs(opts: *mut mongoc_transaction_opt_t, read_prefs: *const mongoc_read_prefs_t) -> ();
+        pub fn mongoc_transaction_opts_destroy(opts: *mut mongoc_transaction_opt_t) -> ();
+    }
+
+    // Server description
+    pub enum mongoc_server_description_t {}
+    #[repr(C)]
+    pub struct mongoc_host_list_t {
+        pub next: *mut mongoc_host_list_t,
+        pub host: [::libc::c_char; 256usize],
+        pub host_and_port: [::libc::c_char; 262usize],
+        pub port: uint16_t,
+        pub family: ::libc::c_int,
+        pub padding: [*mut ::libc::c_void; 4usize],
+    }
+    extern "C" {
+        pub fn mongoc_server_description_host(description: *const mongoc_server_description_t) -> *mut mongoc_host_list_t;
+        pub fn mongoc_server_description_round_trip_time(description: *const mongoc_server_description_t) -> int64_t;
+        pub fn mongoc_server_descriptions_destroy_all(sds: *mut *mut mongoc_server_description_t, n: size_t) -> ();
+        pub fn mongoc_server_description_destroy(description: *mut mongoc_server_description_t) -> ();
+        pub fn mongoc_server_description_type(description: *const mongoc_server_description_t) -> *const ::libc::c_char;
+        pub fn mongoc_server_description_hello_response(description: *const mongoc_server_description_t) -> *const bson_t;
+    }
+
+    // APM
+    pub enum mongoc_apm_callbacks_t {}
+    pub enum mongoc_apm_server_changed_t {}
+    pub enum mongoc_apm_server_heartbeat_started_t {}
+    pub enum mongoc_apm_server_heartbeat_succeeded_t {}
+    pub enum mongoc_apm_server_heartbeat_failed_t {}
+    pub type mongoc_apm_server_changed_cb_t = Option<unsafe extern "C" fn(event: *const mongoc_apm_server_changed_t)>;
+    pub type mongoc_apm_server_heartbeat_started_cb_t = Option<unsafe extern "C" fn(event: *const mongoc_apm_server_heartbeat_started_t)>;
+    pub type mongoc_apm_server_heartbeat_succeeded_cb_t = Option<unsafe extern "C" fn(event: *const mongoc_apm_server_heartbeat_succeeded_t)>;
+    pub type mongoc_apm_server_heartbeat_failed_cb_t = Option<unsafe extern "C" fn(event: *const mongoc_apm_server_heartbeat_failed_t)>;
+    extern "C" {
+        pub fn mongoc_apm_callbacks_new() -> *mut mongoc_apm_callbacks_t;
+        pub fn mongoc_apm_callbacks_destroy(callbacks: *mut mongoc_apm_callbacks_t) -> ();
+        pub fn mongoc_apm_set_server_changed_cb(callbacks: *mut mongoc_apm_callbacks_t, cb: mongoc_apm_server_changed_cb_t) -> ();
+        pub fn mongoc_apm_set_server_heartbeat_started_cb(callbacks: *mut mongoc_apm_callbacks_t, cb: mongoc_apm_server_heartbeat_started_cb_t) -> ();
+        pub fn mongoc_apm_set_server_heartbeat_succeeded_cb(callbacks: *mut mongoc_apm_callbacks_t, cb: mongoc_apm_server_heartbeat_succeeded_cb_t) -> ();
+        pub fn mongoc_apm_set_server_heartbeat_failed_cb(callbacks: *mut mongoc_apm_callbacks_t, cb: mongoc_apm_server_heartbeat_failed_cb_t) -> ();
+        pub fn mongoc_apm_server_changed_get_host(event: *const mongoc_apm_server_changed_t) -> *const mongoc_host_list_t;
+        pub fn mongoc_apm_server_changed_get_previous_description(event: *const mongoc_apm_server_changed_t) -> *const mongoc_server_description_t;
+        pub fn mongoc_apm_server_changed_get_new_description(event: *const mongoc_apm_server_changed_t) -> *const mongoc_server_description_t;
+        pub fn mongoc_apm_server_changed_get_context(event: *const mongoc_apm_server_changed_t) -> *mut ::libc::c_void;
+        pub fn mongoc_apm_server_heartbeat_started_get_host(event: *const mongoc_apm_server_heartbeat_started_t) -> *const mongoc_host_list_t;
+        pub fn mongoc_apm_server_heartbeat_started_get_awaited(event: *const mongoc_apm_server_heartbeat_started_t) -> u8;
+        pub fn mongoc_apm_server_heartbeat_started_get_context(event: *const mongoc_apm_server_heartbeat_started_t) -> *mut ::libc::c_void;
+        pub fn mongoc_apm_server_heartbeat_succeeded_get_duration(event: *const mongoc_apm_server_heartbeat_succeeded_t) -> int64_t;
+        pub fn mongoc_apm_server_heartbeat_succeeded_get_host(event: *const mongoc_apm_server_heartbeat_succeeded_t) -> *const mongoc_host_list_t;
+        pub fn mongoc_apm_server_heartbeat_succeeded_get_awaited(event: *const mongoc_apm_server_heartbeat_succeeded_t) -> u8;
+        pub fn mongoc_apm_server_heartbeat_succeeded_get_context(event: *const mongoc_apm_server_heartbeat_succeeded_t) -> *mut ::libc::c_void;
+        pub fn mongoc_apm_server_heartbeat_failed_get_duration(event: *const mongoc_apm_server_heartbeat_failed_t) -> int64_t;
+        pub fn mongoc_apm_server_heartbeat_failed_get_error(event: *const mongoc_apm_server_heartbeat_failed_t, error: *mut bson_error_t) -> ();
+        pub fn mongoc_apm_server_heartbeat_failed_get_host(event: *const mongoc_apm_server_heartbeat_failed_t) -> *const mongoc_host_list_t;
+        pub fn mongoc_apm_server_heartbeat_failed_get_awaited(event: *const mongoc_apm_server_heartbeat_failed_t) -> u8;
+        pub fn mongoc_apm_server_heartbeat_failed_get_context(event: *const mongoc_apm_server_heartbeat_failed_t) -> *mut ::libc::c_void;
+        pub fn mongoc_client_pool_set_apm_callbacks(pool: *mut mongoc_client_pool_t, callbacks: *mut mongoc_apm_callbacks_t, context: *mut ::libc::c_void) -> u8;
     }
 
     // Collection
@@ -143,17 +318,51 @@ pub mod bindings {
         pub fn mongoc_collection_aggregate(collection: *mut mongoc_collection_t, flags: mongoc_query_flags_t, pipeline: *const bson_t, options: *const bson_t, read_prefs: *const mongoc_read_prefs_t) -> *mut mongoc_cursor_t;
         pub fn mongoc_collection_command(collection: *mut mongoc_collection_t, flags: mongoc_query_flags_t, skip: uint32_t, limit: uint32_t, batch_size: uint32_t, command: *const bson_t, fields: *const bson_t, read_prefs: *const mongoc_read_prefs_t) -> *mut mongoc_cursor_t;
         pub fn mongoc_collection_command_simple(collection: *mut mongoc_collection_t, command: *const bson_t, read_prefs: *const mongoc_read_prefs_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_collection_count_documents(collection: *mut mongoc_collection_t, filter: *const bson_t, opts: *const bson_t, read_prefs: *const mongoc_read_prefs_t, reply: *mut bson_t, error: *mut bson_error_t) -> int64_t;
         pub fn mongoc_collection_count_with_opts(collection: *mut mongoc_collection_t, flags: mongoc_query_flags_t, query: *const bson_t, skip: int64_t, limit: int64_t, opts: *const bson_t, read_prefs: *const mongoc_read_prefs_t, error: *mut bson_error_t) -> int64_t;
         pub fn mongoc_collection_create_bulk_operation(collection: *mut mongoc_collection_t, ordered: u8, write_concern: *const mongoc_write_concern_t) -> *mut mongoc_bulk_operation_t;
         pub fn mongoc_collection_drop(collection: *mut mongoc_collection_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_collection_watch(collection: *const mongoc_collection_t, pipeline: *const bson_t, opts: *const bson_t) -> *mut mongoc_change_stream_t;
+        pub fn mongoc_collection_find_indexes_with_opts(collection: *mut mongoc_collection_t, opts: *const bson_t) -> *mut mongoc_cursor_t;
+        pub fn mongoc_collection_find_with_opts(collection: *mut mongoc_collection_t, filter: *const bson_t, opts: *const bson_t, read_prefs: *const mongoc_read_prefs_t) -> *mut mongoc_cursor_t;
         pub fn mongoc_collection_find(collection: *mut mongoc_collection_t, flags: mongoc_query_flags_t, skip: uint32_t, limit: uint32_t, batch_size: uint32_t, query: *const bson_t, fields: *const bson_t, read_prefs: *const mongoc_read_prefs_t) -> *mut mongoc_cursor_t;
+        pub fn mongoc_collection_find_and_modify_with_opts(collection: *mut mongoc_collection_t, query: *const bson_t, opts: *const mongoc_find_and_modify_opts_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_collection_find_and_modify(collection: *mut mongoc_collection_t, query: *const bson_t, sort: *const bson_t, update: *const bson_t, fields: *const bson_t, _remove: u8, upsert: u8, _new: u8, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_collection_get_name(collection: *mut mongoc_collection_t) -> *const ::libc::c_char;
+        pub fn mongoc_collection_insert_one(collection: *mut mongoc_collection_t, document: *const bson_t, opts: *const bson_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_collection_insert(collection: *mut mongoc_collection_t, flags: mongoc_insert_flags_t, document: *const bson_t, write_concern: *const mongoc_write_concern_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_collection_remove(collection: *mut mongoc_collection_t, flags: mongoc_remove_flags_t, selector: *const bson_t, write_concern: *const mongoc_write_concern_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_collection_save(collection: *mut mongoc_collection_t, document: *const bson_t, write_concern: *const mongoc_write_concern_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_collection_rename(collection: *mut mongoc_collection_t, new_db: *const ::libc::c_char, new_name: *const ::libc::c_char, drop_target_before_rename: u8, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_collection_replace_one(collection: *mut mongoc_collection_t, selector: *const bson_t, replacement: *const bson_t, opts: *const bson_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_collection_update_one(collection: *mut mongoc_collection_t, selector: *const bson_t, update: *const bson_t, opts: *const bson_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_collection_update_many(collection: *mut mongoc_collection_t, selector: *const bson_t, update: *const bson_t, opts: *const bson_t, reply: *mut bson_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_collection_update(collection: *mut mongoc_collection_t, flags: mongoc_update_flags_t, selector: *const bson_t, update: *const bson_t, write_concern: *const mongoc_write_concern_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_collection_destroy(collection: *mut mongoc_collection_t) -> ();
+        pub fn mongoc_collection_get_read_concern(collection: *const mongoc_collection_t) -> *const mongoc_read_concern_t;
+        pub fn mongoc_collection_get_write_concern(collection: *const mongoc_collection_t) -> *const mongoc_write_concern_t;
+        pub fn mongoc_collection_set_write_concern(collection: *mut mongoc_collection_t, write_concern: *const mongoc_write_concern_t) -> ();
+        pub fn mongoc_collection_get_read_prefs(collection: *const mongoc_collection_t) -> *const mongoc_read_prefs_t;
+        pub fn mongoc_collection_set_read_prefs(collection: *mut mongoc_collection_t, read_prefs: *const mongoc_read_prefs_t) -> ();
+        pub fn mongoc_collection_set_read_concern(collection: *mut mongoc_collection_t, read_concern: *const mongoc_read_concern_t) -> ();
+    }
+
+    // Find and modify
+    pub enum mongoc_find_and_modify_opts_t {}
+    pub type mongoc_find_and_modify_flags_t = ::libc::c_uint;
+    pub const MONGOC_FIND_AND_MODIFY_NONE: ::libc::c_uint = 0;
+    pub const MONGOC_FIND_AND_MODIFY_REMOVE: ::libc::c_uint = 1;
+    pub const MONGOC_FIND_AND_MODIFY_UPSERT: ::libc::c_uint = 2;
+    pub const MONGOC_FIND_AND_MODIFY_RETURN_NEW: ::libc::c_uint = 4;
+    extern "C" {
+        pub fn mongoc_find_and_modify_opts_new() -> *mut mongoc_find_and_modify_opts_t;
+        pub fn mongoc_find_and_modify_opts_set_sort(opts: *mut mongoc_find_and_modify_opts_t, sort: *const bson_t) -> u8;
+        pub fn mongoc_find_and_modify_opts_set_update(opts: *mut mongoc_find_and_modify_opts_t, update: *const bson_t) -> u8;
+        pub fn mongoc_find_and_modify_opts_set_fields(opts: *mut mongoc_find_and_modify_opts_t, fields: *const bson_t) -> u8;
+        pub fn mongoc_find_and_modify_opts_set_flags(opts: *mut mongoc_find_and_modify_opts_t, flags: mongoc_find_and_modify_flags_t) -> u8;
+        pub fn mongoc_find_and_modify_opts_set_bypass_document_validation(opts: *mut mongoc_find_and_modify_opts_t, bypass: u8) -> u8;
+        pub fn mongoc_find_and_modify_opts_append(opts: *mut mongoc_find_and_modify_opts_t, extra: *const bson_t) -> u8;
+        pub fn mongoc_find_and_modify_opts_destroy(opts: *mut mongoc_find_and_modify_opts_t) -> ();
     }
 
     // Cursor
@@ -164,6 +373,45 @@ pub mod bindings {
         pub fn mongoc_cursor_error(cursor: *mut mongoc_cursor_t, error: *mut bson_error_t) -> u8;
         pub fn mongoc_cursor_next(cursor: *mut mongoc_cursor_t, bson: *mut *const bson_t) -> u8;
         pub fn mongoc_cursor_destroy(cursor: *mut mongoc_cursor_t) -> ();
+        pub fn mongoc_cursor_clone(cursor: *const mongoc_cursor_t) -> *mut mongoc_cursor_t;
+        pub fn mongoc_cursor_set_batch_size(cursor: *mut mongoc_cursor_t, batch_size: uint32_t) -> ();
+        pub fn mongoc_cursor_get_batch_size(cursor: *const mongoc_cursor_t) -> uint32_t;
+        pub fn mongoc_cursor_get_id(cursor: *const mongoc_cursor_t) -> int64_t;
+        pub fn mongoc_cursor_set_max_await_time_ms(cursor: *mut mongoc_cursor_t, max_await_time_ms: uint32_t) -> ();
+        pub fn mongoc_cursor_get_max_await_time_ms(cursor: *const mongoc_cursor_t) -> uint32_t;
+        pub fn mongoc_cursor_get_hint(cursor: *const mongoc_cursor_t) -> uint32_t;
+        pub fn mongoc_cursor_set_hint(cursor: *mut mongoc_cursor_t, server_id: uint32_t) -> u8;
+    }
+
+    // Change stream
+    pub enum mongoc_change_stream_t {}
+    extern "C" {
+        pub fn mongoc_change_stream_next(stream: *mut mongoc_change_stream_t, bson: *mut *const bson_t) -> u8;
+        pub fn mongoc_change_stream_error_document(stream: *const mongoc_change_stream_t, error: *mut bson_error_t, reply: *mut *const bson_t) -> u8;
+        pub fn mongoc_change_stream_get_resume_token(stream: *mut mongoc_change_stream_t) -> *const bson_t;
+        pub fn mongoc_change_stream_destroy(stream: *mut mongoc_change_stream_t) -> ();
+    }
+
+    // Stream
+    pub enum mongoc_stream_t {}
+    extern "C" {
+        pub fn mongoc_stream_read(stream: *mut mongoc_stream_t, buf: *mut ::libc::c_void, count: size_t, min_bytes: size_t, timeout_msec: int32_t) -> ssize_t;
+        pub fn mongoc_stream_write(stream: *mut mongoc_stream_t, buf: *mut ::libc::c_void, count: size_t, timeout_msec: int32_t) -> ssize_t;
+        pub fn mongoc_stream_close(stream: *mut mongoc_stream_t) -> ::libc::c_int;
+        pub fn mongoc_stream_destroy(stream: *mut mongoc_stream_t) -> ();
+    }
+
+    // GridFS bucket
+    pub enum mongoc_gridfs_bucket_t {}
+    extern "C" {
+        pub fn mongoc_gridfs_bucket_new(db: *mut mongoc_database_t, opts: *const bson_t, read_prefs: *const mongoc_read_prefs_t, error: *mut bson_error_t) -> *mut mongoc_gridfs_bucket_t;
+        pub fn mongoc_gridfs_bucket_open_upload_stream_with_id(bucket: *mut mongoc_gridfs_bucket_t, file_id: *const bson_value_t, filename: *const ::libc::c_char, opts: *const bson_t, error: *mut bson_error_t) -> *mut mongoc_stream_t;
+        pub fn mongoc_gridfs_bucket_open_download_stream(bucket: *mut mongoc_gridfs_bucket_t, file_id: *const bson_value_t, error: *mut bson_error_t) -> *mut mongoc_stream_t;
+        pub fn mongoc_gridfs_bucket_find(bucket: *mut mongoc_gridfs_bucket_t, filter: *const bson_t, opts: *const bson_t) -> *mut mongoc_cursor_t;
+        pub fn mongoc_gridfs_bucket_delete_by_id(bucket: *mut mongoc_gridfs_bucket_t, file_id: *const bson_value_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_gridfs_bucket_stream_error(stream: *mut mongoc_stream_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_gridfs_bucket_abort_upload(stream: *mut mongoc_stream_t) -> u8;
+        pub fn mongoc_gridfs_bucket_destroy(bucket: *mut mongoc_gridfs_bucket_t) -> ();
     }
 
     // Bulk operation
@@ -175,6 +423,12 @@ pub mod bindings {
         pub fn mongoc_bulk_operation_replace_one(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, document: *const bson_t, upsert: u8) -> ();
         pub fn mongoc_bulk_operation_update(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, document: *const bson_t, upsert: u8) -> ();
         pub fn mongoc_bulk_operation_update_one(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, document: *const bson_t, upsert: u8) -> ();
+        pub fn mongoc_bulk_operation_remove_many_with_opts(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, opts: *const bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_bulk_operation_remove_one_with_opts(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, opts: *const bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_bulk_operation_replace_one_with_opts(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, document: *const bson_t, opts: *const bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_bulk_operation_update_many_with_opts(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, document: *const bson_t, opts: *const bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_bulk_operation_update_one_with_opts(bulk: *mut mongoc_bulk_operation_t, selector: *const bson_t, document: *const bson_t, opts: *const bson_t, error: *mut bson_error_t) -> u8;
+        pub fn mongoc_bulk_operation_set_bypass_document_validation(bulk: *mut mongoc_bulk_operation_t, bypass: u8) -> ();
         pub fn mongoc_bulk_operation_execute(bulk: *mut mongoc_bulk_operation_t, reply: *mut bson_t, error: *mut bson_error_t) -> uint32_t;
         pub fn mongoc_bulk_operation_destroy(bulk: *mut mongoc_bulk_operation_t) -> ();
     }
@@ -219,6 +473,10 @@ pub mod bindings {
     pub const MONGOC_ERROR_COLLECTION: ::libc::c_uint = 12;
     pub const MONGOC_ERROR_GRIDFS: ::libc::c_uint = 13;
     pub const MONGOC_ERROR_SCRAM: ::libc::c_uint = 14;
+    pub const MONGOC_ERROR_SERVER_SELECTION: ::libc::c_uint = 15;
+    pub const MONGOC_ERROR_WRITE_CONCERN: ::libc::c_uint = 16;
+    pub const MONGOC_ERROR_SERVER: ::libc::c_uint = 17;
+    pub const MONGOC_ERROR_TRANSACTION: ::libc::c_uint = 18;
     pub const MONGOC_ERROR_STREAM_INVALID_TYPE: ::libc::c_uint = 1;
     pub const MONGOC_ERROR_STREAM_INVALID_STATE: ::libc::c_uint = 2;
     pub const MONGOC_ERROR_STREAM_NAME_RESOLUTION: ::libc::c_uint = 3;
@@ -253,6 +511,7 @@ pub mod bindings {
     pub const MONGOC_ERROR_PROTOCOL_ERROR: ::libc::c_uint = 17;
     pub const MONGOC_ERROR_WRITE_CONCERN_ERROR: ::libc::c_uint = 64;
     pub const MONGOC_ERROR_DUPLICATE_KEY: ::libc::c_uint = 11000;
+    pub const MONGOC_ERROR_GRIDFS_BUCKET_FILE_NOT_FOUND: ::libc::c_uint = 55;
 }
 
 #[cfg(test)]
@@ -0,0 +1,128 @@
+//! Monitoring of the servers a client pool is connected to.
+//!
+//! Register a handler with `set_server_event_handler` on a `ClientPool` to receive an event for
+//! every heartbeat the driver sends to a server and every change in the type of a server, for
+//! example when a secondary becomes primary or a node becomes unreachable.
+
+use std::ffi::CStr;
+use std::panic;
+use std::time::Duration;
+
+use crate::mongoc::bindings;
+
+use super::BsoncError;
+
+/// Heartbeat the driver sent to a server to monitor it.
+#[derive(Debug)]
+pub struct ServerHeartbeatEvent {
+    /// Host and port of the server
+    pub host:     String,
+    /// Time it took for the heartbeat to complete, zero when it started
+    pub duration: Duration,
+    /// Whether the heartbeat waited for the server to report a change, instead of polling
+    pub awaited:  bool
+}
+
+/// Change in the type of a server, such as from `RSSecondary` to `RSPrimary`. The type is
+/// `Unknown` when a server is unreachable.
+#[derive(Debug)]
+pub struct ServerChangedEvent {
+    /// Host and port of the server
+    pub host:          String,
+    /// Type of the server before the change
+    pub previous_type: String,
+    /// Type of the server after the change
+    pub new_type:      String
+}
+
+/// Events that are delivered to a server event handler.
+#[derive(Debug)]
+pub enum ServerEvent {
+    HeartbeatStarted(ServerHeartbeatEvent),
+    HeartbeatSucceeded(ServerHeartbeatEvent),
+    HeartbeatFailed(ServerHeartbeatEvent, BsoncError),
+    Changed(ServerChangedEvent)
+}
+
+/// Handler that is called for server events. Heartbeats are sent from a background thread,
+/// so the handler must be safe to call from any thread.
+pub type ServerEventHandler = Box<dyn Fn(ServerEvent) + Send + Sync>;
+
+/// Create the mongoc callbacks that deliver server events to the handler, only for internal usage.
+/// The handler is passed as the context of the callbacks, so it must outlive the pool.
+pub(crate) fn server_callbacks() -> *mut bindings::mongoc_apm_callbacks_t {
+    unsafe {
+        let callbacks = bindings::mongoc_apm_callbacks_new();
+        assert!(!callbacks.is_null());
+        bindings::mongoc_apm_set_server_changed_cb(callbacks, Some(server_changed));
+        bindings::mongoc_apm_set_server_heartbeat_started_cb(callbacks, Some(heartbeat_started));
+        bindings::mongoc_apm_set_server_heartbeat_succeeded_cb(callbacks, Some(heartbeat_succeeded));
+        bindings::mongoc_apm_set_server_heartbeat_failed_cb(callbacks, Some(heartbeat_failed));
+        callbacks
+    }
+}
+
+unsafe fn deliver(context: *mut ::libc::c_void, event: ServerEvent) {
+    assert!(!context.is_null());
+    let handler = &*(context as *const ServerEventHandler);
+    // Unwinding into the C driver is undefined behaviour, so a panicking handler loses the event.
+    let _ = panic::catch_unwind(panic::AssertUnwindSafe(|| handler(event)));
+}
+
+unsafe fn host_and_port(host: *const bindings::mongoc_host_list_t) -> String {
+    CStr::from_ptr((*host).host_and_port.as_ptr()).to_string_lossy().into_owned()
+}
+
+unsafe fn server_type(description: *const bindings::mongoc_server_description_t) -> String {
+    CStr::from_ptr(bindings::mongoc_server_description_type(description)).to_string_lossy().into_owned()
+}
+
+unsafe extern "C" fn server_changed(event: *const bindings::mongoc_apm_server_changed_t) {
+    let changed = ServerChangedEvent {
+        host:          host_and_port(bindings::mongoc_apm_server_changed_get_host(event)),
+        previous_type: server_type(bindings::mongoc_apm_server_changed_get_previous_description(event)),
+        new_type:      server_type(bindings::mongoc_apm_server_changed_get_new_description(event))
+    };
+    deliver(
+        bindings::mongoc_apm_server_changed_get_context(event),
+        ServerEvent::Changed(changed)
+    );
+}
+
+unsafe extern "C" fn heartbeat_started(event: *const bindings::mongoc_apm_server_heartbeat_started_t) {
+    let heartbeat = ServerHeartbeatEvent {
+        host:     host_and_port(bindings::mongoc_apm_server_heartbeat_started_get_host(event)),
+        duration: Duration::from_micros(0),
+        awaited:  bindings::mongoc_apm_server_heartbeat_started_get_awaited(event) == 1
+    };
+    deliver(
+        bindings::mongoc_apm_server_heartbeat_started_get_context(event),
+        ServerEvent::HeartbeatStarted(heartbeat)
+    );
+}
+
+unsafe extern "C" fn heartbeat_succeeded(event: *const bindings::mongoc_apm_server_heartbeat_succeeded_t) {
+    let heartbeat = ServerHeartbeatEvent {
+        host:     host_and_port(bindings::mongoc_apm_server_heartbeat_succeeded_get_host(event)),
+        duration: Duration::from_micros(bindings::mongoc_apm_server_heartbeat_succeeded_get_duration(event) as u64),
+        awaited:  bindings::mongoc_apm_server_heartbeat_succeeded_get_awaited(event) == 1
+    };
+    deliver(
+        bindings::mongoc_apm_server_heartbeat_succeeded_get_context(event),
+        ServerEvent::HeartbeatSucceeded(heartbeat)
+    );
+}
+
+unsafe extern "C" fn heartbeat_failed(event: *const bindings::mongoc_apm_server_heartbeat_failed_t) {
+    let heartbeat = ServerHeartbeatEvent {
+        host:     host_and_port(bindings::mongoc_apm_server_heartbeat_failed_get_host(event)),
+        duration: Duration::from_micros(bindings::mongoc_apm_server_heartbeat_failed_get_duration(event) as u64),
+        awaited:  bindings::mongoc_apm_server_heartbeat_failed_get_awaited(event) == 1
+    };
+    let mut error = BsoncError::empty();
+    bindings::mongoc_apm_server_heartbeat_failed_get_error(event, error.mut_inner());
+    deliver(
+        bindings::mongoc_apm_server_heartbeat_failed_get_context(event),
+        ServerEvent::HeartbeatFailed(heartbeat, error)
+    );
+}
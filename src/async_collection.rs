@@ -0,0 +1,119 @@
+//! Access to a MongoDB collection from async code.
+//!
+//! Only available if the `async` feature is enabled. All operations in this driver block the
+//! calling thread until the server responds, which starves the worker threads of an async runtime.
+//! `AsyncCollection` runs operations on Tokio's blocking thread pool instead and returns futures.
+//!
+//! Every operation pops a client from the `ClientPool` for its duration. If the pool is exhausted
+//! the operation waits for a client while holding on to a blocking thread. Tokio allows at most
+//! 512 blocking threads by default, so keep the uri's `maxPoolSize` well below Tokio's
+//! `max_blocking_threads` to leave room for other blocking work. If `waitQueueTimeoutMS` is set
+//! in the uri and no client becomes available within that time the operation returns an
+//! `io::ErrorKind::TimedOut` error.
+
+use std::io;
+use std::panic;
+use std::sync::Arc;
+
+use bson::Document;
+use tokio::task;
+
+use super::{Result,MongoError};
+use super::CommandAndFindOptions;
+use super::client::ClientPool;
+use super::collection::{Collection,InsertOneResult};
+
+/// Provides async access to a collection for the most common operations.
+///
+/// An async collection owns a reference to the pool, so it can be cloned cheaply and
+/// moved into tasks.
+#[derive(Clone)]
+pub struct AsyncCollection {
+    pool:       Arc<ClientPool>,
+    db:         String,
+    collection: String
+}
+
+impl AsyncCollection {
+    /// Create an async collection that pops clients from the given pool.
+    pub fn new<DBT: Into<String>, CT: Into<String>>(
+        pool:       Arc<ClientPool>,
+        db:         DBT,
+        collection: CT
+    ) -> AsyncCollection {
+        AsyncCollection {
+            pool:       pool,
+            db:         db.into(),
+            collection: collection.into()
+        }
+    }
+
+    /// Simplified version of `command` that returns the first document immediately.
+    pub async fn command_simple(&self, command: Document) -> Result<Document> {
+        self.run(move |collection| collection.command_simple(command, None)).await
+    }
+
+    /// Execute a count query on the underlying collection.
+    pub async fn count(&self, query: Document) -> Result<i64> {
+        self.run(move |collection| collection.count(&query, None)).await
+    }
+
+    /// Execute a query on the underlying collection and collect all results.
+    pub async fn find(&self, query: Document) -> Result<Vec<Document>> {
+        self.run(move |collection| {
+            collection.find(&query, None)?.collect()
+        }).await
+    }
+
+    /// Execute a query on the underlying collection and return the first result.
+    pub async fn find_one(&self, query: Document) -> Result<Option<Document>> {
+        self.run(move |collection| {
+            let mut options = CommandAndFindOptions::default();
+            options.limit = 1;
+            collection.find(&query, Some(&options))?.next().transpose()
+        }).await
+    }
+
+    /// Insert document into collection, an `_id` is generated if the document has none.
+    pub async fn insert_one(&self, document: Document) -> Result<InsertOneResult> {
+        self.run(move |collection| collection.insert_one(&document, None)).await
+    }
+
+    /// Remove documents in the given collection that match selector.
+    pub async fn remove(&self, selector: Document) -> Result<()> {
+        self.run(move |collection| collection.remove(&selector, None)).await
+    }
+
+    /// Updates a single document in the collection that matches selector.
+    pub async fn update(&self, selector: Document, update: Document) -> Result<()> {
+        self.run(move |collection| collection.update(&selector, &update, None)).await
+    }
+
+    async fn run<F, T>(&self, operation: F) -> Result<T>
+        where F: FnOnce(&Collection) -> Result<T> + Send + 'static,
+              T: Send + 'static
+    {
+        let pool       = self.pool.clone();
+        let db         = self.db.clone();
+        let collection = self.collection.clone();
+
+        let handle = task::spawn_blocking(move || {
+            let client = match pool.try_pop_wait() {
+                Some(client) => client,
+                None => return Err(MongoError::Io(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "Timed out waiting for a client from the pool"
+                )))
+            };
+            let collection = client.get_collection(db, collection);
+            operation(&collection)
+        });
+
+        match handle.await {
+            Ok(result) => result,
+            Err(error) if error.is_panic() => panic::resume_unwind(error.into_panic()),
+            // The task was cancelled because the runtime is shutting down
+            Err(error) => Err(MongoError::Io(io::Error::new(io::ErrorKind::Interrupted, error)))
+        }
+    }
+}
@@ -56,8 +56,26 @@ impl Bsonc {
         })
     }
 
-    /// Decode a bson from the C side to a document
+    /// Decode a bson from the C side to a document. Invalid UTF-8 in strings is replaced
+    /// with the replacement character, this is what the driver does unless strict decoding
+    /// is requested.
     pub fn as_document(&self) -> Result<bson::Document> {
+        let mut slice = self.as_slice();
+
+        #[allow(deprecated)]
+        // Ignore this deprecation, this is hopefully the last
+        // time we update bson.
+        Ok(bson::Document::from_reader_utf8_lossy(&mut slice)?)
+    }
+
+    /// Decode a bson from the C side to a document, returning an error if a string
+    /// contains invalid UTF-8.
+    pub fn as_document_strict(&self) -> Result<bson::Document> {
+        let mut slice = self.as_slice();
+        Ok(bson::Document::from_reader(&mut slice)?)
+    }
+
+    fn as_slice(&self) -> &[u8] {
         assert!(!self.inner.is_null());
 
         // This pointer should not be modified or freed
@@ -70,14 +88,9 @@ impl Bsonc {
             bson.len
         } as usize;
 
-        let mut slice = unsafe {
+        unsafe {
             slice::from_raw_parts(data_ptr, data_len)
-        };
-
-        #[allow(deprecated)]
-        // Ignore this deprecation, this is hopefully the last
-        // time we update bson.
-        Ok(bson::Document::from_reader_utf8_lossy(&mut slice)?)
+        }
     }
 
     pub fn as_json(&self) -> String {
@@ -101,6 +114,19 @@ impl Bsonc {
     }
 }
 
+impl Clone for Bsonc {
+    /// Copies the underlying bson, the copy is always destroyed on drop.
+    fn clone(&self) -> Bsonc {
+        assert!(!self.inner.is_null());
+        let inner = unsafe { bindings::bson_copy(self.inner) };
+        assert!(!inner.is_null());
+        Bsonc {
+            inner: inner,
+            destroy_inner_on_drop: true
+        }
+    }
+}
+
 impl fmt::Debug for Bsonc {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Bsonc: {}", self.as_json())
@@ -144,6 +170,18 @@ mod tests {
 
         let decoded = bsonc.as_document().unwrap();
         assert_eq!(decoded.get_str("key").unwrap(), "��");
+
+        assert!(bsonc.as_document_strict().is_err());
+    }
+
+    #[test]
+    fn test_bsonc_clone() {
+        let document = doc! { "key": "value" };
+        let bsonc = super::Bsonc::from_document(&document).unwrap();
+        let cloned = bsonc.clone();
+        drop(bsonc);
+
+        assert_eq!(document, cloned.as_document().unwrap());
     }
 
     #[test]
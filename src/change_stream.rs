@@ -0,0 +1,222 @@
+//! Access to a MongoDB change stream.
+
+use std::ptr;
+use std::time::{Duration,Instant};
+
+use crate::mongoc::bindings;
+use bson::{Document,Timestamp};
+
+use super::BsoncError;
+use super::bsonc::Bsonc;
+use super::client::Client;
+use super::collection::Collection;
+use super::database::Database;
+
+use super::Result;
+
+/// Options to configure a change stream.
+pub struct ChangeStreamOptions {
+    /// Set to `updateLookup` to include the current version of the document in update events
+    pub full_document:           Option<String>,
+    /// Resume token of the event to resume after
+    pub resume_after:            Option<Document>,
+    /// Resume token of the event to start after, unlike `resume_after` this also works after an invalidate event
+    pub start_after:             Option<Document>,
+    /// Only return changes that happened at or after this operation time
+    pub start_at_operation_time: Option<Timestamp>,
+    /// Number of events in each batch, the server default is used if not set
+    pub batch_size:              Option<u32>,
+    /// How long the server waits for new events before returning an empty batch
+    pub max_await_time:          Option<Duration>,
+    /// How long iterating waits for the next event before returning None, waits indefinitely if not set
+    pub timeout:                 Option<Duration>
+}
+
+impl ChangeStreamOptions {
+    /// Default options used if none are provided.
+    pub fn default() -> ChangeStreamOptions {
+        ChangeStreamOptions {
+            full_document:           None,
+            resume_after:            None,
+            start_after:             None,
+            start_at_operation_time: None,
+            batch_size:              None,
+            max_await_time:          None,
+            timeout:                 None
+        }
+    }
+
+    pub(crate) fn to_document(&self) -> Document {
+        let mut document = Document::new();
+        if let Some(ref full_document) = self.full_document {
+            document.insert("fullDocument", full_document.clone());
+        }
+        if let Some(ref resume_after) = self.resume_after {
+            document.insert("resumeAfter", resume_after.clone());
+        }
+        if let Some(ref start_after) = self.start_after {
+            document.insert("startAfter", start_after.clone());
+        }
+        if let Some(start_at_operation_time) = self.start_at_operation_time {
+            document.insert("startAtOperationTime", start_at_operation_time);
+        }
+        if let Some(batch_size) = self.batch_size {
+            document.insert("batchSize", batch_size as i32);
+        }
+        if let Some(max_await_time) = self.max_await_time {
+            document.insert("maxAwaitTimeMS", max_await_time.as_millis() as i64);
+        }
+        document
+    }
+}
+
+#[doc(hidden)]
+pub enum CreatedBy<'a> {
+    Client(&'a Client<'a>),
+    Database(&'a Database<'a>),
+    Collection(&'a Collection<'a>)
+}
+
+/// Provides access to the changes in a collection, database or deployment as they happen.
+///
+/// A change stream is created by calling `watch` on a `Collection`, `Database` or `Client`.
+/// It implements the `Iterator` trait, every item is a change event document. Change streams
+/// are only available on replica sets and sharded clusters.
+pub struct ChangeStream<'a> {
+    _created_by: CreatedBy<'a>,
+    inner:       *mut bindings::mongoc_change_stream_t,
+    timeout:     Option<Duration>,
+    failed:      bool
+}
+
+impl<'a> ChangeStream<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        created_by: CreatedBy<'a>,
+        inner:      *mut bindings::mongoc_change_stream_t,
+        timeout:    Option<Duration>
+    ) -> ChangeStream<'a> {
+        assert!(!inner.is_null());
+        ChangeStream {
+            _created_by: created_by,
+            inner:       inner,
+            timeout:     timeout,
+            failed:      false
+        }
+    }
+
+    /// Create a change stream with the given watch function, which is passed the
+    /// pipeline and options.
+    pub(crate) fn watch<F>(
+        created_by: CreatedBy<'a>,
+        pipeline:   &[Document],
+        options:    Option<&ChangeStreamOptions>,
+        watch_fn:   F
+    ) -> Result<ChangeStream<'a>>
+        where F: FnOnce(*const bindings::bson_t, *const bindings::bson_t) -> *mut bindings::mongoc_change_stream_t
+    {
+        let default_options = ChangeStreamOptions::default();
+        let options         = options.unwrap_or(&default_options);
+
+        let pipeline_bsonc = Bsonc::from_document(&doc! { "pipeline": pipeline.to_vec() })?;
+        let opts_bsonc     = Bsonc::from_document(&options.to_document())?;
+        let inner          = watch_fn(pipeline_bsonc.inner(), opts_bsonc.inner());
+
+        let change_stream = ChangeStream::new(created_by, inner, options.timeout);
+
+        // Invalid options are reported when creating the change stream.
+        let error = change_stream.error();
+        if error.is_empty() {
+            Ok(change_stream)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Token to resume this change stream after the last event that was returned, pass it
+    /// as `resumeAfter` in the options of `watch` to continue where this stream left off.
+    ///
+    /// Returns None if the server has not provided a token yet, which is the case before
+    /// the first event for older servers.
+    pub fn resume_token(&self) -> Option<Document> {
+        assert!(!self.inner.is_null());
+        let token_ptr = unsafe { bindings::mongoc_change_stream_get_resume_token(self.inner) };
+        if token_ptr.is_null() {
+            return None
+        }
+        Bsonc::from_ptr(token_ptr).as_document().ok()
+    }
+
+    fn error(&self) -> BsoncError {
+        assert!(!self.inner.is_null());
+        let mut error = BsoncError::empty();
+        unsafe {
+            bindings::mongoc_change_stream_error_document(
+                self.inner,
+                error.mut_inner(),
+                ptr::null_mut()
+            );
+        }
+        error
+    }
+}
+
+impl<'a> Iterator for ChangeStream<'a> {
+    type Item = Result<Document>;
+
+    /// Wait for the next change event. If a timeout was given to `watch` this returns
+    /// None when no event arrived within the timeout, otherwise it keeps waiting.
+    ///
+    /// An error the change stream can't resume from is returned once, after that this
+    /// returns None.
+    fn next(&mut self) -> Option<Self::Item> {
+        assert!(!self.inner.is_null());
+
+        if self.failed {
+            return None
+        }
+
+        let started_at = Instant::now();
+        loop {
+            // The C driver sets this pointer to the event, which it owns.
+            let mut bson_ptr: *const bindings::bson_t = ptr::null();
+            let success = unsafe {
+                bindings::mongoc_change_stream_next(
+                    self.inner,
+                    &mut bson_ptr
+                )
+            };
+
+            if success == 1 {
+                assert!(!bson_ptr.is_null());
+                let bsonc = Bsonc::from_ptr(bson_ptr);
+                match bsonc.as_document() {
+                    Ok(document) => return Some(Ok(document)),
+                    Err(error)   => return Some(Err(error.into()))
+                }
+            }
+
+            let error = self.error();
+            if !error.is_empty() {
+                self.failed = true;
+                return Some(Err(error.into()))
+            }
+
+            // No event arrived within the server's await time.
+            if let Some(timeout) = self.timeout {
+                if started_at.elapsed() >= timeout {
+                    return None
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Drop for ChangeStream<'a> {
+    fn drop(&mut self) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_change_stream_destroy(self.inner);
+        }
+    }
+}
@@ -4,25 +4,39 @@
 
 use std::borrow::Cow;
 use std::fmt;
+use std::env;
 use std::ffi::{CStr,CString};
-use std::path::PathBuf;
+use std::path::{Path,PathBuf};
 use std::mem;
 use std::ptr;
 use std::io;
-use std::fs::File;
+use std::fs;
+use std::fs::{File,OpenOptions};
+use std::io::Write;
+use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize,Ordering};
+use std::time::Duration;
 
 use crate::mongoc::bindings;
 
-use bson::Document;
+use bson::{doc,Bson,Document};
 
 use super::Result;
-use super::BsoncError;
+use super::{BsoncError,InvalidParamsError,ReplyError};
+use super::apm;
+use super::apm::{ServerEvent,ServerEventHandler};
+use super::take_strv;
 use super::bsonc::Bsonc;
+use super::change_stream;
+use super::change_stream::{ChangeStream,ChangeStreamOptions};
 use super::collection;
 use super::collection::Collection;
 use super::database;
 use super::database::Database;
+use super::gridfs::GridFs;
 use super::read_prefs::ReadPrefs;
+use super::session::{ClientSession,SessionOptions};
 
 /// Pool that allows usage of clients out of a single pool from multiple threads.
 ///
@@ -37,6 +51,9 @@ pub struct ClientPool {
     // loses access to resources it needs.
     uri:          Uri,
     _ssl_options: Option<SslOptions>,
+    // The server event handler is the context of the apm callbacks, so it needs to be present
+    // for the lifetime of this pool as well.
+    server_event_handler: Option<Box<ServerEventHandler>>,
     inner:         *mut bindings::mongoc_client_pool_t
 }
 
@@ -65,6 +82,7 @@ impl ClientPool {
         ClientPool {
             uri:          uri,
             _ssl_options: ssl_options,
+            server_event_handler: None,
             inner:        pool
         }
     }
@@ -74,16 +92,133 @@ impl ClientPool {
         &self.uri
     }
 
+    /// Set the maximum number of clients in this pool, `pop` blocks when they are all in use.
+    /// This overrides `maxPoolSize` from the uri.
+    ///
+    /// Must be set before the first client is popped from the pool.
+    pub fn set_max_size(&self, max: u32) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_client_pool_max_size(self.inner, max);
+        }
+    }
+
+    /// Set the number of idle clients this pool keeps around when clients are pushed back,
+    /// clients above this number are destroyed.
+    ///
+    /// Must be set before the first client is popped from the pool.
+    pub fn set_min_size(&self, min: u32) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_client_pool_min_size(self.inner, min);
+        }
+    }
+
+    /// Set a handler that receives an event for every server heartbeat and every change in the
+    /// type of a server, see the `apm` module.
+    ///
+    /// This can only be called once, before the first client is popped from the pool. Returns an
+    /// `InvalidParamsError` otherwise.
+    pub fn set_server_event_handler<F>(&mut self, handler: F) -> Result<()>
+        where F: Fn(ServerEvent) + Send + Sync + 'static
+    {
+        assert!(!self.inner.is_null());
+        if self.server_event_handler.is_some() {
+            return Err(InvalidParamsError.into())
+        }
+
+        let handler: Box<ServerEventHandler> = Box::new(Box::new(handler));
+        let success = unsafe {
+            let callbacks = apm::server_callbacks();
+            let success = bindings::mongoc_client_pool_set_apm_callbacks(
+                self.inner,
+                callbacks,
+                &*handler as *const ServerEventHandler as *mut ::libc::c_void
+            );
+            bindings::mongoc_apm_callbacks_destroy(callbacks);
+            success
+        };
+
+        if success == 1 {
+            self.server_event_handler = Some(handler);
+            Ok(())
+        } else {
+            Err(InvalidParamsError.into())
+        }
+    }
+
+    /// Set the application name that is sent to the server when connecting, it shows up in
+    /// the server logs and in `currentOp`. The name can also be set with `appName` in the uri.
+    ///
+    /// This can only be called once, before the first client is popped from the pool. Returns an
+    /// `InvalidParamsError` if mongoc rejects the name, because it was already set or is longer
+    /// than 128 bytes.
+    ///
+    /// This is set on the pool rather than on a `Client`, mongoc doesn't allow setting the name
+    /// on a client that belongs to a pool. All clients popped from the pool use the name.
+    pub fn set_appname(&self, name: &str) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let name_cstring = CString::new(name)?;
+        let success = unsafe {
+            bindings::mongoc_client_pool_set_appname(self.inner, name_cstring.as_ptr())
+        };
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(InvalidParamsError.into())
+        }
+    }
+
     /// Retrieve a client from the client pool, possibly blocking until one is available.
+    ///
+    /// The pool is limited to the uri's `maxPoolSize`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `waitQueueTimeoutMS` is set in the uri and no client becomes available within
+    /// that time. Use `try_pop_wait` to handle the timeout instead.
     pub fn pop(&self) -> Client {
+        self.try_pop_wait().expect("Timed out waiting for a client from the pool")
+    }
+
+    /// Retrieve a client from the client pool, possibly blocking until one is available.
+    /// Returns none if `waitQueueTimeoutMS` is set in the uri and no client became available
+    /// within that time, without the setting this waits indefinitely like `pop`.
+    pub fn try_pop_wait(&self) -> Option<Client<'_>> {
         assert!(!self.inner.is_null());
         let client = unsafe { bindings::mongoc_client_pool_pop(self.inner) };
-        Client{
-            client_pool: self,
-            inner:       client
+        if client.is_null() {
+            None
+        } else {
+            Some(Client{
+                client_pool: self,
+                inner:       client
+            })
+        }
+    }
+
+    /// Retrieve a client from the client pool if one is available, returns none immediately
+    /// if all clients are in use.
+    pub fn try_pop(&self) -> Option<Client<'_>> {
+        assert!(!self.inner.is_null());
+        let client = unsafe { bindings::mongoc_client_pool_try_pop(self.inner) };
+        if client.is_null() {
+            None
+        } else {
+            Some(Client{
+                client_pool: self,
+                inner:       client
+            })
         }
     }
 
+    /// Pop a client, run the closure with it and return the client to the pool once the
+    /// closure is done. Call this from every thread that needs a connection, for example
+    /// with a pool shared by reference in a `thread::scope`.
+    pub fn with_client<F, R>(&self, f: F) -> R where F: FnOnce(Client<'_>) -> R {
+        f(self.pop())
+    }
+
     /// Return a client back to the client pool, called from drop of client.
     unsafe fn push(&self, mongo_client: *mut bindings::mongoc_client_t) {
         assert!(!self.inner.is_null());
@@ -128,19 +263,27 @@ pub struct SslOptions {
     _ca_dir_cstring:       Option<CString>,
     crl_file:              Option<PathBuf>,
     _crl_file_cstring:     Option<CString>,
-    weak_cert_validation: bool
+    weak_cert_validation: bool,
+    allow_invalid_hostname: bool,
+    // Temporary files written by `from_pem_bytes`, shared with clones.
+    temp_files:           Vec<Arc<TempFile>>
 }
 
 impl SslOptions {
     /// Create a new ssl options instance that can be used to configured
     /// a `ClientPool`.
+    ///
+    /// `weak_cert_validation` disables validation of the server certificate entirely.
+    /// `allow_invalid_hostname` only skips checking that the certificate was issued for the
+    /// host that is connected to, for example when connecting to a node by its IP address.
     pub fn new(
-        pem_file:             Option<PathBuf>,
-        pem_password:         Option<String>,
-        ca_file:              Option<PathBuf>,
-        ca_dir:               Option<PathBuf>,
-        crl_file:             Option<PathBuf>,
-        weak_cert_validation: bool
+        pem_file:               Option<PathBuf>,
+        pem_password:           Option<String>,
+        ca_file:                Option<PathBuf>,
+        ca_dir:                 Option<PathBuf>,
+        crl_file:               Option<PathBuf>,
+        weak_cert_validation:   bool,
+        allow_invalid_hostname: bool
     ) -> io::Result<SslOptions> {
         let pem_file_cstring     = Self::cstring_from_path(&pem_file)?;
         let pem_password_cstring = Self::cstring_from_string(&pem_password);
@@ -170,6 +313,8 @@ impl SslOptions {
                 None => ptr::null()
             },
             weak_cert_validation: weak_cert_validation as u8,
+            allow_invalid_hostname: allow_invalid_hostname as u8,
+            internal: ptr::null_mut(),
             padding: unsafe { mem::zeroed() }
         };
 
@@ -185,10 +330,57 @@ impl SslOptions {
             _ca_dir_cstring:       ca_dir_cstring,
             crl_file:              crl_file,
             _crl_file_cstring:     crl_file_cstring,
-            weak_cert_validation:  weak_cert_validation
+            weak_cert_validation:  weak_cert_validation,
+            allow_invalid_hostname: allow_invalid_hostname,
+            temp_files:            Vec::new()
         })
     }
 
+    /// Create ssl options from a PEM certificate and key and an optional CA certificate that are
+    /// in memory, for example when they are fetched from a secrets manager.
+    ///
+    /// mongoc can only read certificates from files, so they are written to temporary files that
+    /// only the current user can read. The files are removed when these options and all their
+    /// clones are dropped.
+    pub fn from_pem_bytes(
+        pem:                    &[u8],
+        pem_password:           Option<String>,
+        ca:                     Option<&[u8]>,
+        weak_cert_validation:   bool,
+        allow_invalid_hostname: bool
+    ) -> io::Result<SslOptions> {
+        let pem_file = TempFile::new(pem)?;
+        let ca_file = match ca {
+            Some(ca) => Some(TempFile::new(ca)?),
+            None => None
+        };
+
+        let mut ssl_options = SslOptions::new(
+            Some(pem_file.path.clone()),
+            pem_password,
+            ca_file.as_ref().map(|f| f.path.clone()),
+            None,
+            None,
+            weak_cert_validation,
+            allow_invalid_hostname
+        )?;
+        ssl_options.temp_files.push(Arc::new(pem_file));
+        if let Some(ca_file) = ca_file {
+            ssl_options.temp_files.push(Arc::new(ca_file));
+        }
+        Ok(ssl_options)
+    }
+
+    /// Get the path of the PEM file.
+    pub fn pem_file(&self) -> Option<&PathBuf> {
+        self.pem_file.as_ref()
+    }
+
+    /// Get the path of the CA file.
+    pub fn ca_file(&self) -> Option<&PathBuf> {
+        self.ca_file.as_ref()
+    }
+
     fn cstring_from_path(path: &Option<PathBuf>) -> io::Result<Option<CString>> {
         match path {
             &Some(ref p) => {
@@ -213,14 +405,53 @@ impl SslOptions {
 
 impl Clone for SslOptions {
     fn clone(&self) -> SslOptions {
-        SslOptions::new(
+        let mut ssl_options = SslOptions::new(
             self.pem_file.clone(),
             self.pem_password.clone(),
             self.ca_file.clone(),
             self.ca_dir.clone(),
             self.crl_file.clone(),
-            self.weak_cert_validation
-        ).unwrap()
+            self.weak_cert_validation,
+            self.allow_invalid_hostname
+        ).unwrap();
+        ssl_options.temp_files = self.temp_files.clone();
+        ssl_options
+    }
+}
+
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// File in the temp dir that is removed on drop.
+struct TempFile {
+    path: PathBuf
+}
+
+impl TempFile {
+    fn new(contents: &[u8]) -> io::Result<TempFile> {
+        let path = env::temp_dir().join(format!(
+            "mongo-rust-driver-{}-{}.pem",
+            process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        // Only remove the file once it is known to be ours
+        let temp_file = TempFile { path: path };
+        file.write_all(contents)?;
+        Ok(temp_file)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
     }
 }
 
@@ -229,6 +460,33 @@ impl Clone for SslOptions {
 /// It maintains management of underlying sockets and routing to individual nodes based on
 /// `ReadPrefs` or `WriteConcern`. Clients cannot be shared between threads, pop a new one from
 /// a `ClientPool` in every thread that needs a connection instead.
+///
+/// Moving a client to another thread does not compile:
+///
+/// ```compile_fail
+/// use std::thread;
+/// use mongo_driver::client::{ClientPool,Uri};
+///
+/// let uri    = Uri::new("mongodb://localhost:27017/").unwrap();
+/// let pool   = ClientPool::new(uri, None);
+/// let client = pool.pop();
+/// thread::scope(|scope| {
+///     scope.spawn(move || client.ping());
+/// });
+/// ```
+///
+/// Use `ClientPool::with_client` in the thread instead:
+///
+/// ```no_run
+/// use std::thread;
+/// use mongo_driver::client::{ClientPool,Uri};
+///
+/// let uri  = Uri::new("mongodb://localhost:27017/").unwrap();
+/// let pool = ClientPool::new(uri, None);
+/// thread::scope(|scope| {
+///     scope.spawn(|| pool.with_client(|client| client.ping()));
+/// });
+/// ```
 pub struct Client<'a> {
     client_pool: &'a ClientPool,
     inner:       *mut bindings::mongoc_client_t
@@ -275,6 +533,14 @@ impl<'a> Client<'a> {
         Database::new(database::CreatedBy::OwnedClient(self), coll)
     }
 
+    /// Get a GridFS bucket in a database. Files are stored in the `<prefix>.files` and
+    /// `<prefix>.chunks` collections, `fs` is the conventional prefix.
+    pub fn get_gridfs<S: Into<Vec<u8>>>(&'a self, db: S, prefix: &str) -> Result<GridFs<'a>> {
+        assert!(!self.inner.is_null());
+        let database = unsafe { self.database_ptr(db.into()) };
+        GridFs::new(self, database, prefix)
+    }
+
     unsafe fn database_ptr(&self, db: Vec<u8>) -> *mut bindings::mongoc_database_t {
         let db_cstring = CString::new(db).unwrap();
         bindings::mongoc_client_get_database(
@@ -314,6 +580,53 @@ impl<'a> Client<'a> {
         }
     }
 
+    /// Run a command against a database and return the reply, without the need to get a
+    /// `Database` first. If the command fails the error contains the reply of the server.
+    pub fn command_simple<S: Into<Vec<u8>>>(
+        &self,
+        db:         S,
+        command:    Document,
+        read_prefs: Option<&ReadPrefs>
+    ) -> Result<Document> {
+        assert!(!self.inner.is_null());
+
+        let db_cstring = CString::new(db)?;
+
+        // Bsonc to store the reply
+        let mut reply = Bsonc::new();
+        // Empty error that might be filled
+        let mut error = BsoncError::empty();
+
+        let success = unsafe {
+            bindings::mongoc_client_command_simple(
+                self.inner,
+                db_cstring.as_ptr(),
+                Bsonc::from_document(&command)?.inner(),
+                match read_prefs {
+                    Some(ref prefs) => prefs.inner(),
+                    None => ptr::null()
+                },
+                reply.mut_inner(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            match reply.as_document() {
+                Ok(document) => return Ok(document),
+                Err(error)   => return Err(error.into())
+            }
+        } else {
+            Err(ReplyError::from_reply(error, &reply))
+        }
+    }
+
+    /// Check whether the server is reachable by running a `ping` command against the `admin`
+    /// database. The error contains the connection diagnostics of the driver if it is not.
+    pub fn ping(&self) -> Result<()> {
+        self.read_command_with_opts("admin", &doc! { "ping": 1 }, None, None).map(|_| ())
+    }
+
     pub fn read_command_with_opts<S: Into<Vec<u8>>>(&self,
                                   db: S,
                                   command: &Document,
@@ -355,6 +668,195 @@ impl<'a> Client<'a> {
             Err(error.into())
         }
     }
+
+    /// Get the names of all databases on the server.
+    ///
+    /// Options such as a `filter` or `authorizedDatabases` are passed on to the `listDatabases` command.
+    pub fn get_database_names(&self, opts: Option<&Document>) -> Result<Vec<String>> {
+        assert!(!self.inner.is_null());
+
+        let opts_bsonc = match opts {
+            Some(o) => Some(Bsonc::from_document(o)?),
+            None => None
+        };
+        // Empty error that might be filled
+        let mut error = BsoncError::empty();
+
+        let names = unsafe {
+            bindings::mongoc_client_get_database_names_with_opts(
+                self.inner,
+                match opts_bsonc {
+                    Some(ref o) => o.inner(),
+                    None => ptr::null()
+                },
+                error.mut_inner()
+            )
+        };
+
+        if names.is_null() {
+            Err(error.into())
+        } else {
+            Ok(unsafe { take_strv(names) })
+        }
+    }
+
+    /// Start a session on this client. Operations that are passed the session in their
+    /// options run in it, which makes them causally consistent by default.
+    pub fn start_session(&'a self, options: Option<&SessionOptions>) -> Result<ClientSession<'a>> {
+        assert!(!self.inner.is_null());
+
+        let default_options = SessionOptions::default();
+        let options         = options.unwrap_or(&default_options);
+
+        // Empty error that might be filled
+        let mut error = BsoncError::empty();
+
+        let session_ptr = unsafe {
+            let opts = bindings::mongoc_session_opts_new();
+            bindings::mongoc_session_opts_set_causal_consistency(opts, options.causal_consistency as u8);
+            let session_ptr = bindings::mongoc_client_start_session(self.inner, opts, error.mut_inner());
+            bindings::mongoc_session_opts_destroy(opts);
+            session_ptr
+        };
+
+        if session_ptr.is_null() {
+            Err(error.into())
+        } else {
+            Ok(ClientSession::new(self, session_ptr))
+        }
+    }
+
+    /// Watch all databases in the deployment for changes. The pipeline contains aggregation
+    /// stages, such as `$match`, that filter or transform the change events on the server.
+    pub fn watch(
+        &'a self,
+        pipeline: &[Document],
+        options:  Option<&ChangeStreamOptions>
+    ) -> Result<ChangeStream<'a>> {
+        assert!(!self.inner.is_null());
+        ChangeStream::watch(
+            change_stream::CreatedBy::Client(self),
+            pipeline,
+            options,
+            |pipeline, opts| unsafe { bindings::mongoc_client_watch(self.inner, pipeline, opts) }
+        )
+    }
+
+    /// Round-trip time the driver measured for a server during its heartbeats.
+    ///
+    /// The host should be in `host:port` form, as it appears in the topology. Returns `None` if the
+    /// server is not part of the topology or has not been checked yet. Servers are discovered lazily,
+    /// so this will only return a value after an operation has been performed.
+    pub fn server_rtt(&self, host: &str) -> Option<Duration> {
+        assert!(!self.inner.is_null());
+
+        let mut n = 0;
+        let descriptions = unsafe {
+            bindings::mongoc_client_get_server_descriptions(self.inner, &mut n)
+        };
+        if descriptions.is_null() {
+            return None
+        }
+
+        let mut rtt = None;
+        for i in 0..n as usize {
+            unsafe {
+                let description = *descriptions.add(i);
+                let host_list = bindings::mongoc_server_description_host(description);
+                let host_and_port = CStr::from_ptr((*host_list).host_and_port.as_ptr());
+                if host_and_port.to_string_lossy() == host {
+                    let rtt_ms = bindings::mongoc_server_description_round_trip_time(description);
+                    if rtt_ms >= 0 {
+                        rtt = Some(Duration::from_millis(rtt_ms as u64));
+                    }
+                    break;
+                }
+            }
+        }
+
+        unsafe {
+            bindings::mongoc_server_descriptions_destroy_all(descriptions, n);
+        }
+
+        rtt
+    }
+
+    /// Maximum size in bytes of a document the server accepts, usually 16MB.
+    ///
+    /// The limit is reported by the server in the handshake when connecting, so this connects
+    /// first if needed. If no server can be reached the default limit of the driver is returned.
+    pub fn max_bson_size(&self) -> u32 {
+        self.handshake_size_limit("maxBsonObjectSize", DEFAULT_MAX_BSON_SIZE)
+    }
+
+    /// Maximum size in bytes of a message to the server, usually 48MB. A batch of writes
+    /// is split into multiple messages when it is larger.
+    ///
+    /// The limit is reported by the server in the handshake when connecting, so this connects
+    /// first if needed. If no server can be reached the default limit of the driver is returned.
+    pub fn max_message_size(&self) -> u32 {
+        self.handshake_size_limit("maxMessageSizeBytes", DEFAULT_MAX_MESSAGE_SIZE)
+    }
+
+    /// Select the server an operation would be sent to, connecting first if needed. Pass
+    /// `for_writes` to select the server writes go to, otherwise a server is selected with
+    /// the read prefs, or the primary if there are none.
+    ///
+    /// Returns an error if no suitable server is found within `serverSelectionTimeoutMS`.
+    pub fn select_server(&self, for_writes: bool, read_prefs: Option<&ReadPrefs>) -> Result<ServerDescription> {
+        let description = self.select_server_description(for_writes, read_prefs)?;
+        let server_description = unsafe { ServerDescription::from_ptr(description) };
+        unsafe {
+            bindings::mongoc_server_description_destroy(description);
+        }
+        Ok(server_description)
+    }
+
+    /// Select a server like `select_server`, the returned description must be destroyed.
+    fn select_server_description(
+        &self,
+        for_writes: bool,
+        read_prefs: Option<&ReadPrefs>
+    ) -> Result<*mut bindings::mongoc_server_description_t> {
+        assert!(!self.inner.is_null());
+
+        let mut error = BsoncError::empty();
+        let description = unsafe {
+            bindings::mongoc_client_select_server(
+                self.inner,
+                for_writes as u8,
+                match read_prefs {
+                    Some(ref prefs) => prefs.inner(),
+                    None => ptr::null()
+                },
+                error.mut_inner()
+            )
+        };
+        if description.is_null() {
+            return Err(error.into())
+        }
+        Ok(description)
+    }
+
+    /// Read a size limit from the handshake reply of the server writes go to, which works
+    /// for every server version without another round trip.
+    fn handshake_size_limit(&self, key: &str, default: u32) -> u32 {
+        let description = match self.select_server_description(true, None) {
+            Ok(description) => description,
+            Err(_) => return default
+        };
+        let reply = Bsonc::from_ptr(unsafe {
+            bindings::mongoc_server_description_hello_response(description)
+        }).as_document();
+        unsafe {
+            bindings::mongoc_server_description_destroy(description);
+        }
+        match reply.ok().and_then(|reply| reply.get(key).cloned()) {
+            Some(Bson::Int32(value)) if value > 0 => value as u32,
+            Some(Bson::Int64(value)) if value > 0 => value as u32,
+            _ => default
+        }
+    }
 }
 
 impl<'a> Drop for Client<'a> {
@@ -366,6 +868,77 @@ impl<'a> Drop for Client<'a> {
     }
 }
 
+/// Type of a server, as discovered by monitoring it.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum ServerType {
+    Standalone,
+    Mongos,
+    PossiblePrimary,
+    RSPrimary,
+    RSSecondary,
+    RSArbiter,
+    RSOther,
+    RSGhost,
+    LoadBalancer,
+    Unknown
+}
+
+impl ServerType {
+    fn from_name(name: &str) -> ServerType {
+        match name {
+            "Standalone"      => ServerType::Standalone,
+            "Mongos"          => ServerType::Mongos,
+            "PossiblePrimary" => ServerType::PossiblePrimary,
+            "RSPrimary"       => ServerType::RSPrimary,
+            "RSSecondary"     => ServerType::RSSecondary,
+            "RSArbiter"       => ServerType::RSArbiter,
+            "RSOther"         => ServerType::RSOther,
+            "RSGhost"         => ServerType::RSGhost,
+            "LoadBalancer"    => ServerType::LoadBalancer,
+            _                 => ServerType::Unknown
+        }
+    }
+
+    /// Whether writes can be sent to this type of server.
+    pub fn is_writable(&self) -> bool {
+        match *self {
+            ServerType::Standalone | ServerType::Mongos | ServerType::RSPrimary | ServerType::LoadBalancer => true,
+            _ => false
+        }
+    }
+}
+
+/// Description of a server that was selected for an operation.
+#[derive(Clone,Debug)]
+pub struct ServerDescription {
+    /// Host and port of the server
+    pub host:            String,
+    /// Type of the server, such as primary or secondary
+    pub server_type:     ServerType,
+    /// Average round trip time to the server, if it has been measured
+    pub round_trip_time: Option<Duration>
+}
+
+impl ServerDescription {
+    unsafe fn from_ptr(description: *const bindings::mongoc_server_description_t) -> ServerDescription {
+        assert!(!description.is_null());
+        let host      = bindings::mongoc_server_description_host(description);
+        let type_name = CStr::from_ptr(bindings::mongoc_server_description_type(description));
+        let rtt_ms    = bindings::mongoc_server_description_round_trip_time(description);
+        ServerDescription {
+            host:            CStr::from_ptr((*host).host_and_port.as_ptr()).to_string_lossy().into_owned(),
+            server_type:     ServerType::from_name(&type_name.to_string_lossy()),
+            round_trip_time: if rtt_ms >= 0 { Some(Duration::from_millis(rtt_ms as u64)) } else { None }
+        }
+    }
+}
+
+// Maximum pool size mongoc uses if none is set in the uri.
+const DEFAULT_MAX_POOL_SIZE: i32 = 100;
+// Size limits mongoc uses until a server reported its own.
+const DEFAULT_MAX_BSON_SIZE: u32 = 16 * 1024 * 1024;
+const DEFAULT_MAX_MESSAGE_SIZE: u32 = 48_000_000;
+
 /// Abstraction on top of MongoDB connection URI format.
 pub struct Uri {
     inner: *mut bindings::mongoc_uri_t
@@ -414,7 +987,254 @@ impl Uri {
         }
     }
 
-    // TODO add various methods that are available on uri
+    /// Username to authenticate with, if any.
+    pub fn get_username<'a>(&'a self) -> Option<Cow<'a, str>> {
+        assert!(!self.inner.is_null());
+        unsafe { Self::optional_str(bindings::mongoc_uri_get_username(self.inner)) }
+    }
+
+    /// Password to authenticate with, if any.
+    pub fn get_password<'a>(&'a self) -> Option<Cow<'a, str>> {
+        assert!(!self.inner.is_null());
+        unsafe { Self::optional_str(bindings::mongoc_uri_get_password(self.inner)) }
+    }
+
+    /// Database to authenticate against, set with `authSource`.
+    ///
+    /// If this is not set mongoc falls back to the database in the uri, and then to `admin`.
+    pub fn get_auth_source<'a>(&'a self) -> Option<Cow<'a, str>> {
+        assert!(!self.inner.is_null());
+        unsafe { Self::optional_str(bindings::mongoc_uri_get_auth_source(self.inner)) }
+    }
+
+    /// Mechanism to authenticate with, set with `authMechanism`, such as `SCRAM-SHA-256`.
+    ///
+    /// The `GSSAPI` mechanism for Kerberos needs the `sasl` feature.
+    pub fn get_auth_mechanism<'a>(&'a self) -> Option<Cow<'a, str>> {
+        assert!(!self.inner.is_null());
+        unsafe { Self::optional_str(bindings::mongoc_uri_get_auth_mechanism(self.inner)) }
+    }
+
+    /// Get the application name set with `appName`.
+    pub fn get_appname<'a>(&'a self) -> Option<Cow<'a, str>> {
+        assert!(!self.inner.is_null());
+        unsafe { Self::optional_str(bindings::mongoc_uri_get_appname(self.inner)) }
+    }
+
+    unsafe fn optional_str<'a>(ptr: *const ::libc::c_char) -> Option<Cow<'a, str>> {
+        if ptr.is_null() {
+            None
+        } else {
+            let cstr = CStr::from_ptr(ptr);
+            Some(String::from_utf8_lossy(cstr.to_bytes()))
+        }
+    }
+
+    /// Seed hosts in this uri as `host:port` strings.
+    ///
+    /// The hosts of a `mongodb+srv://` uri are only known after the SRV record is resolved
+    /// when connecting, so this returns an empty list for such a uri.
+    pub fn get_hosts(&self) -> Vec<String> {
+        assert!(!self.inner.is_null());
+        let mut hosts = Vec::new();
+        unsafe {
+            let mut host_list = bindings::mongoc_uri_get_hosts(self.inner);
+            while !host_list.is_null() {
+                let host_and_port = CStr::from_ptr((*host_list).host_and_port.as_ptr());
+                hosts.push(host_and_port.to_string_lossy().into_owned());
+                host_list = (*host_list).next;
+            }
+        }
+        hosts
+    }
+
+    /// Host name of a `mongodb+srv://` uri, none for other uris.
+    ///
+    /// The SRV and TXT records of this host are resolved when connecting. The TXT record can set
+    /// `authSource` and `replicaSet`, Atlas for example sets `authSource=admin`. Use the
+    /// `srvServiceName` and `srvMaxHosts` options to configure the resolution.
+    pub fn get_srv_hostname<'a>(&'a self) -> Option<Cow<'a, str>> {
+        assert!(!self.inner.is_null());
+        unsafe { Self::optional_str(bindings::mongoc_uri_get_srv_hostname(self.inner)) }
+    }
+
+    /// Whether connections made with this uri use TLS, set with `tls` or `ssl`.
+    ///
+    /// A `mongodb+srv://` uri uses TLS unless it is disabled explicitly.
+    pub fn is_tls(&self) -> bool {
+        assert!(!self.inner.is_null());
+        unsafe { bindings::mongoc_uri_get_tls(self.inner) == 1 }
+    }
+
+    /// Maximum number of clients in a pool created with this uri, set with `maxPoolSize`.
+    pub fn get_max_pool_size(&self) -> u32 {
+        self.get_option_as_int32("maxpoolsize", DEFAULT_MAX_POOL_SIZE) as u32
+    }
+
+    /// Minimum number of clients a pool created with this uri keeps around, set with `minPoolSize`.
+    pub fn get_min_pool_size(&self) -> u32 {
+        self.get_option_as_int32("minpoolsize", 0) as u32
+    }
+
+    /// How long popping a client waits for one to become available, set with `waitQueueTimeoutMS`.
+    /// Returns None if popping waits indefinitely.
+    pub fn get_wait_queue_timeout(&self) -> Option<Duration> {
+        match self.get_option_as_int32("waitqueuetimeoutms", 0) {
+            timeout if timeout > 0 => Some(Duration::from_millis(timeout as u64)),
+            _ => None
+        }
+    }
+
+    /// Get a boolean option such as `tls`, none if the option is not set.
+    pub fn get_option_bool(&self, key: &str) -> Option<bool> {
+        if !self.has_option(key) {
+            return None
+        }
+        let key_cstring = CString::new(key).unwrap();
+        unsafe {
+            Some(bindings::mongoc_uri_get_option_as_bool(self.inner, key_cstring.as_ptr(), 0) == 1)
+        }
+    }
+
+    /// Get a string option such as `replicaSet`, none if the option is not set.
+    pub fn get_option_utf8<'a>(&'a self, key: &str) -> Option<Cow<'a, str>> {
+        if !self.has_option(key) {
+            return None
+        }
+        let key_cstring = CString::new(key).unwrap();
+        unsafe {
+            Self::optional_str(
+                bindings::mongoc_uri_get_option_as_utf8(self.inner, key_cstring.as_ptr(), ptr::null())
+            )
+        }
+    }
+
+    /// Get an integer option such as `connectTimeoutMS`, none if the option is not set.
+    pub fn get_option_i32(&self, key: &str) -> Option<i32> {
+        if !self.has_option(key) {
+            return None
+        }
+        Some(self.get_option_as_int32(key, 0))
+    }
+
+    /// Set a boolean option such as `retryWrites`. Returns an `InvalidParamsError` if mongoc
+    /// does not know the option or it is not a boolean option.
+    ///
+    /// Options that are set are not reflected in `as_str`.
+    pub fn set_option_bool(&mut self, key: &str, value: bool) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let key_cstring = CString::new(key)?;
+        let success = unsafe {
+            bindings::mongoc_uri_set_option_as_bool(self.inner, key_cstring.as_ptr(), value as u8)
+        };
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(InvalidParamsError.into())
+        }
+    }
+
+    /// Set a string option such as `replicaSet`. Returns an `InvalidParamsError` if mongoc
+    /// does not know the option or it is not a string option.
+    ///
+    /// Options that are set are not reflected in `as_str`.
+    pub fn set_option_utf8(&mut self, key: &str, value: &str) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let key_cstring   = CString::new(key)?;
+        let value_cstring = CString::new(value)?;
+        let success = unsafe {
+            bindings::mongoc_uri_set_option_as_utf8(self.inner, key_cstring.as_ptr(), value_cstring.as_ptr())
+        };
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(InvalidParamsError.into())
+        }
+    }
+
+    /// Set an integer option such as `connectTimeoutMS`. Returns an `InvalidParamsError` if
+    /// mongoc does not know the option or it is not an integer option.
+    ///
+    /// Options that are set are not reflected in `as_str`.
+    pub fn set_option_i32(&mut self, key: &str, value: i32) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let key_cstring = CString::new(key)?;
+        let success = unsafe {
+            bindings::mongoc_uri_set_option_as_int32(self.inner, key_cstring.as_ptr(), value)
+        };
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(InvalidParamsError.into())
+        }
+    }
+
+    /// Set the `connectTimeoutMS` option, how long connecting to a server may take.
+    pub fn set_connect_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.set_timeout_option("connectTimeoutMS", timeout)
+    }
+
+    /// Set the `socketTimeoutMS` option, how long a send or receive on a socket may take.
+    pub fn set_socket_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.set_timeout_option("socketTimeoutMS", timeout)
+    }
+
+    /// Set the `serverSelectionTimeoutMS` option, how long an operation waits for a suitable
+    /// server before failing.
+    pub fn set_server_selection_timeout(&mut self, timeout: Duration) -> Result<()> {
+        // mongoc stores option keys in lowercase
+        self.set_timeout_option("serverselectiontimeoutms", timeout)
+    }
+
+    /// How long an operation waits for a suitable server, set with `serverSelectionTimeoutMS`.
+    /// Returns None if the option is not set and the mongoc default is used.
+    pub fn get_server_selection_timeout(&self) -> Option<Duration> {
+        self.get_option_i32("serverselectiontimeoutms").map(|timeout| Duration::from_millis(timeout as u64))
+    }
+
+    fn set_timeout_option(&mut self, key: &str, timeout: Duration) -> Result<()> {
+        if timeout.as_millis() > i32::MAX as u128 {
+            return Err(InvalidParamsError.into())
+        }
+        self.set_option_i32(key, timeout.as_millis() as i32)
+    }
+
+    /// Set the `tlsCAFile` option, the file with the certificate authorities used to validate
+    /// the server certificate. This is the recommended alternative to `SslOptions`.
+    pub fn set_tls_ca_file(&mut self, path: &Path) -> Result<()> {
+        self.set_option_utf8("tlsCAFile", &path.to_string_lossy())
+    }
+
+    /// Set the `tlsCertificateKeyFile` option, the file with the client certificate and key.
+    pub fn set_tls_certificate_key_file(&mut self, path: &Path) -> Result<()> {
+        self.set_option_utf8("tlsCertificateKeyFile", &path.to_string_lossy())
+    }
+
+    /// Set the `tlsAllowInvalidCertificates` option, which disables validation of the server
+    /// certificate.
+    pub fn set_tls_allow_invalid_certificates(&mut self, allow: bool) -> Result<()> {
+        self.set_option_bool("tlsAllowInvalidCertificates", allow)
+    }
+
+    fn has_option(&self, key: &str) -> bool {
+        assert!(!self.inner.is_null());
+        let key_cstring = CString::new(key).unwrap();
+        unsafe {
+            bindings::mongoc_uri_has_option(self.inner, key_cstring.as_ptr()) == 1
+        }
+    }
+
+    fn get_option_as_int32(&self, option: &str, fallback: i32) -> i32 {
+        assert!(!self.inner.is_null());
+        let option_cstring = CString::new(option).unwrap();
+        unsafe {
+            bindings::mongoc_uri_get_option_as_int32(
+                self.inner,
+                option_cstring.as_ptr(),
+                fallback
+            )
+        }
+    }
 }
 
 impl PartialEq for Uri {
@@ -431,7 +1251,11 @@ impl fmt::Debug for Uri {
 
 impl Clone for Uri {
     fn clone(&self) -> Uri {
-        Uri::new(self.as_str().into_owned()).unwrap()
+        assert!(!self.inner.is_null());
+        // Copy instead of parsing the string again, so options that were set are kept
+        let uri = unsafe { bindings::mongoc_uri_copy(self.inner) };
+        assert!(!uri.is_null());
+        Uri { inner: uri }
     }
 }
 
@@ -446,3 +1270,98 @@ impl Drop for Uri {
 
 unsafe impl Send for Uri { }
 unsafe impl Sync for Uri { }
+
+/// Builds a `Uri` from its parts, escaping them where needed.
+///
+/// ```no_run
+/// use mongo_driver::client::UriBuilder;
+///
+/// let mut builder = UriBuilder::new();
+/// builder.host("localhost", 27017)
+///     .credentials("user", "p@ss")
+///     .database("db")
+///     .option("authSource", "admin");
+/// let uri = builder.build().unwrap();
+/// ```
+#[derive(Default)]
+pub struct UriBuilder {
+    hosts:       Vec<String>,
+    credentials: Option<(String, String)>,
+    database:    Option<String>,
+    options:     Vec<(String, String)>
+}
+
+impl UriBuilder {
+    /// Create a builder without any hosts.
+    pub fn new() -> UriBuilder {
+        UriBuilder::default()
+    }
+
+    /// Add a host to connect to, can be called multiple times to add seed hosts.
+    pub fn host(&mut self, host: &str, port: u16) -> &mut UriBuilder {
+        if host.contains(':') {
+            self.hosts.push(format!("[{}]:{}", host, port));
+        } else {
+            self.hosts.push(format!("{}:{}", host, port));
+        }
+        self
+    }
+
+    /// Set the username and password to authenticate with.
+    pub fn credentials(&mut self, user: &str, pass: &str) -> &mut UriBuilder {
+        self.credentials = Some((user.to_string(), pass.to_string()));
+        self
+    }
+
+    /// Set the database to connect to.
+    pub fn database(&mut self, db: &str) -> &mut UriBuilder {
+        self.database = Some(db.to_string());
+        self
+    }
+
+    /// Add a connection option such as `replicaSet`.
+    pub fn option(&mut self, key: &str, value: &str) -> &mut UriBuilder {
+        self.options.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Assemble the connection string and parse it into a `Uri`.
+    ///
+    /// Returns None if the result is not a valid uri, for example if no host was added.
+    pub fn build(self) -> Option<Uri> {
+        let mut uri_string = String::from("mongodb://");
+        if let Some((ref user, ref pass)) = self.credentials {
+            uri_string.push_str(&percent_encode(user));
+            uri_string.push(':');
+            uri_string.push_str(&percent_encode(pass));
+            uri_string.push('@');
+        }
+        uri_string.push_str(&self.hosts.join(","));
+        uri_string.push('/');
+        if let Some(ref database) = self.database {
+            uri_string.push_str(database);
+        }
+        if !self.options.is_empty() {
+            let options: Vec<String> = self.options.iter()
+                .map(|&(ref key, ref value)| format!("{}={}", key, percent_encode(value)))
+                .collect();
+            uri_string.push('?');
+            uri_string.push_str(&options.join("&"));
+        }
+        Uri::new(uri_string)
+    }
+}
+
+// Escape everything but unreserved characters as described in RFC 3986.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            },
+            _ => encoded.push_str(&format!("%{:02X}", byte))
+        }
+    }
+    encoded
+}
@@ -3,26 +3,56 @@
 //! `Collection` is the main type used when accessing collections.
 
 use std::ptr;
-use std::ffi::CStr;
+use std::ffi::{CStr,CString};
 use std::borrow::Cow;
 use std::time::Duration;
 
 use crate::mongoc::bindings;
 use crate::bsonc;
 
-use bson::Document;
+use bson::{Bson,Document};
+use bson::oid::ObjectId;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 
 use super::{Result,BulkOperationResult,BulkOperationError};
 use super::CommandAndFindOptions;
-use super::{BsoncError,InvalidParamsError};
+use super::{BsoncError,InvalidParamsError,MongoError,ReplyError};
 use super::bsonc::Bsonc;
+use super::change_stream;
+use super::change_stream::{ChangeStream,ChangeStreamOptions};
 use super::client::Client;
 use super::cursor;
-use super::cursor::{Cursor,TailingCursor};
+use super::cursor::{Cursor,TailingCursor,TypedCursor};
 use super::database::Database;
 use super::flags::{Flags,FlagsValue,InsertFlag,QueryFlag,RemoveFlag,UpdateFlag};
+use super::index::IndexModel;
 use super::write_concern::WriteConcern;
-use super::read_prefs::ReadPrefs;
+use super::read_concern::ReadConcern;
+use super::read_prefs::{ReadMode,ReadPrefs};
+use super::session::ClientSession;
+
+/// Legacy query modifiers and the find options they translate to.
+const QUERY_MODIFIERS: &[(&str, &str)] = &[
+    ("$orderby",     "sort"),
+    ("$hint",        "hint"),
+    ("$comment",     "comment"),
+    ("$maxTimeMS",   "maxTimeMS"),
+    ("$max",         "max"),
+    ("$min",         "min"),
+    ("$returnKey",   "returnKey"),
+    ("$showDiskLoc", "showRecordId")
+];
+
+/// Query flags and the find options they translate to.
+const QUERY_FLAG_OPTIONS: &[(QueryFlag, &str)] = &[
+    (QueryFlag::TailableCursor,  "tailable"),
+    (QueryFlag::OplogReplay,     "oplogReplay"),
+    (QueryFlag::NoCursorTimeout, "noCursorTimeout"),
+    (QueryFlag::AwaitData,       "awaitData"),
+    (QueryFlag::Exhaust,         "exhaust"),
+    (QueryFlag::Partial,         "allowPartialResults")
+];
 
 #[doc(hidden)]
 pub enum CreatedBy<'a> {
@@ -42,23 +72,33 @@ pub struct Collection<'a> {
 }
 
 /// Options to configure an aggregate operation.
-pub struct AggregateOptions {
+pub struct AggregateOptions<'s> {
     /// Flags to use
     pub query_flags: Flags<QueryFlag>,
     /// Options for the aggregate
     pub options: Option<Document>,
     /// Read prefs to use
-    pub read_prefs:  Option<ReadPrefs>
+    pub read_prefs:  Option<ReadPrefs>,
+    /// Read concern to use
+    pub read_concern: Option<ReadConcern>,
+    /// Replace invalid UTF-8 in strings in the results with the replacement character, if this
+    /// is false a document with invalid UTF-8 results in a `MongoError::Decoder` instead
+    pub utf8_lossy:   bool,
+    /// Session to run the aggregate in
+    pub session:      Option<&'s ClientSession<'s>>
 }
 
-impl AggregateOptions {
+impl<'s> AggregateOptions<'s> {
     /// Default options that are used if no options are specified
     /// when aggregating.
-    pub fn default() -> AggregateOptions {
+    pub fn default() -> AggregateOptions<'s> {
         AggregateOptions {
             query_flags: Flags::new(),
             options: None,
-            read_prefs: None
+            read_prefs: None,
+            read_concern: None,
+            utf8_lossy: true,
+            session: None
         }
     }
 }
@@ -68,7 +108,9 @@ pub struct BulkOperationOptions {
     /// If the operations must be performed in order
     pub ordered:       bool,
     /// `WriteConcern` to use
-    pub write_concern: WriteConcern
+    pub write_concern: WriteConcern,
+    /// Allow the writes to opt-out of document level validation
+    pub bypass_document_validation: bool
 }
 
 impl BulkOperationOptions {
@@ -77,7 +119,8 @@ impl BulkOperationOptions {
     pub fn default() -> BulkOperationOptions {
         BulkOperationOptions {
             ordered:       false,
-            write_concern: WriteConcern::default()
+            write_concern: WriteConcern::default(),
+            bypass_document_validation: false
         }
     }
 }
@@ -89,7 +132,16 @@ pub struct FindAndModifyOptions {
     /// If the new version of the document should be returned
     pub new:    bool,
     /// The fields to return
-    pub fields: Option<Document>
+    pub fields: Option<Document>,
+    /// Allow the write to opt-out of document level validation
+    pub bypass_document_validation: bool,
+    /// Write concern to use
+    pub write_concern: WriteConcern,
+    /// Collation to use when matching the query, such as `{"locale": "en", "strength": 2}`
+    pub collation:     Option<Document>,
+    /// Filters that determine which array elements an update modifies, such as
+    /// `[{"element.grade": {"$gte": 85}}]` for an update using `$[element]`
+    pub array_filters: Option<Vec<Document>>
 }
 
 impl FindAndModifyOptions {
@@ -98,8 +150,25 @@ impl FindAndModifyOptions {
         FindAndModifyOptions {
             sort:   None,
             new:    false,
-            fields: None
+            fields: None,
+            bypass_document_validation: false,
+            write_concern: WriteConcern::default(),
+            collation:     None,
+            array_filters: None
+        }
+    }
+
+    fn extra_bsonc(&self) -> Result<Bsonc> {
+        let mut extra = Document::new();
+        if let Some(ref collation) = self.collation {
+            extra.insert("collation", collation.clone());
         }
+        if let Some(ref array_filters) = self.array_filters {
+            extra.insert("arrayFilters", array_filters.clone());
+        }
+        let mut extra_bsonc = Bsonc::from_document(&extra)?;
+        self.write_concern.append_to(&mut extra_bsonc)?;
+        Ok(extra_bsonc)
     }
 
     fn fields_bsonc(&self) -> Option<bsonc::Bsonc> {
@@ -131,7 +200,9 @@ pub struct CountOptions {
     /// Optional extra keys to add to the count
     pub opts:        Option<Document>,
     /// Read prefs to use
-    pub read_prefs:  Option<ReadPrefs>
+    pub read_prefs:  Option<ReadPrefs>,
+    /// Collation to use when comparing strings, such as `{"locale": "fr", "strength": 1}`
+    pub collation:   Option<Document>
 }
 
 impl CountOptions {
@@ -142,24 +213,61 @@ impl CountOptions {
             skip:        0,
             limit:       0,
             opts:        None,
-            read_prefs:  None
+            read_prefs:  None,
+            collation:   None
+        }
+    }
+
+    /// Extra keys for the count with the collation added.
+    fn count_opts(&self) -> Option<Document> {
+        match self.collation {
+            Some(ref collation) => {
+                let mut opts = self.opts.clone().unwrap_or_default();
+                opts.insert("collation", collation.clone());
+                Some(opts)
+            },
+            None => self.opts.clone()
         }
     }
 }
 
 /// Options to configure an insert operation.
-pub struct InsertOptions {
+pub struct InsertOptions<'s> {
     /// Flags to use
     pub insert_flags:  Flags<InsertFlag>,
     /// Write concern to use
-    pub write_concern: WriteConcern
+    pub write_concern: WriteConcern,
+    /// Allow the write to opt-out of document level validation
+    pub bypass_document_validation: bool,
+    /// Session to run the insert in
+    pub session:       Option<&'s ClientSession<'s>>
 }
 
-impl InsertOptions {
+impl<'s> InsertOptions<'s> {
     /// Default options used if none are provided.
-    pub fn default() -> InsertOptions {
+    pub fn default() -> InsertOptions<'s> {
         InsertOptions {
             insert_flags:  Flags::new(),
+            write_concern: WriteConcern::default(),
+            bypass_document_validation: false,
+            session:       None
+        }
+    }
+}
+
+/// Options to configure an insert many operation.
+pub struct InsertManyOptions {
+    /// If the documents must be inserted in order, stopping at the first error
+    pub ordered:       bool,
+    /// `WriteConcern` to use
+    pub write_concern: WriteConcern
+}
+
+impl InsertManyOptions {
+    /// Default options used if none are provided.
+    pub fn default() -> InsertManyOptions {
+        InsertManyOptions {
+            ordered:       true,
             write_concern: WriteConcern::default()
         }
     }
@@ -188,7 +296,9 @@ pub struct UpdateOptions {
     /// Flags to use
     pub update_flags:  Flags<UpdateFlag>,
     /// Write concern to use
-    pub write_concern: WriteConcern
+    pub write_concern: WriteConcern,
+    /// Allow the write to opt-out of document level validation
+    pub bypass_document_validation: bool
 }
 
 impl UpdateOptions {
@@ -196,7 +306,84 @@ impl UpdateOptions {
     pub fn default() -> UpdateOptions {
         UpdateOptions {
             update_flags:  Flags::new(),
-            write_concern: WriteConcern::default()
+            write_concern: WriteConcern::default(),
+            bypass_document_validation: false
+        }
+    }
+
+    fn opts(&self) -> Document {
+        let mut opts = Document::new();
+        if self.update_flags.contains(&UpdateFlag::Upsert) {
+            opts.insert("upsert", true);
+        }
+        if self.bypass_document_validation {
+            opts.insert("bypassDocumentValidation", true);
+        }
+        opts
+    }
+}
+
+/// Options to configure a replace operation.
+pub struct ReplaceOptions {
+    /// If a new document should be inserted when no document matches the selector
+    pub upsert:        bool,
+    /// Write concern to use
+    pub write_concern: WriteConcern,
+    /// Allow the write to opt-out of document level validation
+    pub bypass_document_validation: bool
+}
+
+impl ReplaceOptions {
+    /// Default options used if none are provided.
+    pub fn default() -> ReplaceOptions {
+        ReplaceOptions {
+            upsert:        false,
+            write_concern: WriteConcern::default(),
+            bypass_document_validation: false
+        }
+    }
+
+    fn opts(&self) -> Document {
+        let mut opts = Document::new();
+        if self.upsert {
+            opts.insert("upsert", true);
+        }
+        if self.bypass_document_validation {
+            opts.insert("bypassDocumentValidation", true);
+        }
+        opts
+    }
+}
+
+/// Result of an insert one operation.
+#[derive(Debug,PartialEq)]
+pub struct InsertOneResult {
+    /// Id of the inserted document
+    pub inserted_id: Bson
+}
+
+/// Result of an update operation.
+#[derive(Debug,PartialEq)]
+pub struct UpdateResult {
+    /// Number of documents that matched the selector
+    pub matched_count:  i64,
+    /// Number of documents that were modified
+    pub modified_count: i64,
+    /// Id of the document that was inserted, if an upsert took place
+    pub upserted_id:    Option<Bson>
+}
+
+impl UpdateResult {
+    fn from_reply(reply: &Document) -> UpdateResult {
+        let count = |key| match reply.get(key) {
+            Some(&Bson::Int32(count)) => count as i64,
+            Some(&Bson::Int64(count)) => count,
+            _ => 0
+        };
+        UpdateResult {
+            matched_count:  count("matchedCount"),
+            modified_count: count("modifiedCount"),
+            upserted_id:    reply.get("upsertedId").cloned()
         }
     }
 }
@@ -206,7 +393,10 @@ pub struct TailOptions {
     /// Duration to wait before checking for new results
     pub wait_duration: Duration,
     /// Maximum number of retries if there is an error
-    pub max_retries:   u32
+    pub max_retries:   u32,
+    /// How long the server waits for new results before returning an empty batch,
+    /// the server default is used if not set
+    pub max_await_time: Option<Duration>
 }
 
 impl TailOptions {
@@ -214,7 +404,26 @@ impl TailOptions {
     pub fn default() -> TailOptions {
         TailOptions {
             wait_duration: Duration::from_millis(500),
-            max_retries:  5
+            max_retries:  5,
+            max_await_time: None
+        }
+    }
+}
+
+/// Options to configure a text search.
+pub struct TextSearchOptions<'s> {
+    /// Field to project the text score into, the score is not returned if this is not set
+    pub score_field:  Option<String>,
+    /// Options for the underlying find
+    pub find_options: CommandAndFindOptions<'s>
+}
+
+impl<'s> TextSearchOptions<'s> {
+    /// Default options used if none are provided.
+    pub fn default() -> TextSearchOptions<'s> {
+        TextSearchOptions {
+            score_field:  None,
+            find_options: CommandAndFindOptions::default()
         }
     }
 }
@@ -243,15 +452,24 @@ impl<'a> Collection<'a> {
         let default_options = AggregateOptions::default();
         let options         = options.unwrap_or(&default_options);
 
+        let mut options_bsonc = match options.options {
+            Some(ref o) => Some(Bsonc::from_document(o)?),
+            None => None
+        };
+        if let Some(ref read_concern) = options.read_concern {
+            read_concern.append_to(options_bsonc.get_or_insert_with(Bsonc::new))?;
+        }
+        if let Some(session) = options.session {
+            session.append_to(options_bsonc.get_or_insert_with(Bsonc::new))?;
+        }
+
         let cursor_ptr = unsafe {
             bindings::mongoc_collection_aggregate(
                 self.inner,
                 options.query_flags.flags(),
                 Bsonc::from_document(pipeline)?.inner(),
-                match options.options {
-                    Some(ref o) => {
-                        Bsonc::from_document(o)?.inner()
-                    },
+                match options_bsonc {
+                    Some(ref o) => o.inner(),
                     None => ptr::null()
                 },
                 match options.read_prefs {
@@ -265,15 +483,33 @@ impl<'a> Collection<'a> {
             return Err(InvalidParamsError.into())
         }
 
-        Ok(Cursor::new(
+        let mut cursor = Cursor::new(
             cursor::CreatedBy::Collection(self),
             cursor_ptr,
             None
-        ))
+        );
+        cursor.set_utf8_lossy(options.utf8_lossy);
+        Ok(cursor)
+    }
+
+    /// Run an aggregation that ends in a write stage, `$out` or `$merge`, and wait until it
+    /// is done. Such a pipeline returns no documents, this drains the cursor and returns an error
+    /// if the aggregation failed, which is otherwise only noticed when iterating.
+    pub fn aggregate_to_collection(
+        &'a self,
+        pipeline: &Document,
+        options:  Option<&AggregateOptions>
+    ) -> Result<()> {
+        for document in self.aggregate(pipeline, options)? {
+            document?;
+        }
+        Ok(())
     }
 
     /// Execute a command on the collection.
     /// This is performed lazily and therefore requires calling `next` on the resulting cursor.
+    /// Commands can't run in a session or with the read concern of the options, an
+    /// `InvalidParamsError` is returned if either is set.
     pub fn command(
         &'a self,
         command: Document,
@@ -285,6 +521,12 @@ impl<'a> Collection<'a> {
         let options         = options.unwrap_or(&default_options);
         let fields_bsonc    = options.fields_bsonc();
 
+        // The legacy command API has no opts to run the command in a session or with a
+        // read concern
+        if options.session.is_some() || options.read_concern.is_some() {
+            return Err(InvalidParamsError.into())
+        }
+
         let cursor_ptr = unsafe {
             bindings::mongoc_collection_command(
                 self.inner,
@@ -292,7 +534,7 @@ impl<'a> Collection<'a> {
                 options.skip,
                 options.limit,
                 options.batch_size,
-                Bsonc::from_document(&command)?.inner(),
+                Bsonc::from_document(&options.command_with_max_time(command))?.inner(),
                 match fields_bsonc {
                     Some(ref f) => f.inner(),
                     None => ptr::null()
@@ -308,14 +550,17 @@ impl<'a> Collection<'a> {
             return Err(InvalidParamsError.into())
         }
 
-        Ok(Cursor::new(
+        let mut cursor = Cursor::new(
             cursor::CreatedBy::Collection(self),
             cursor_ptr,
             fields_bsonc
-        ))
+        );
+        cursor.set_utf8_lossy(options.utf8_lossy);
+        Ok(cursor)
     }
 
     /// Simplified version of `command` that returns the first document immediately.
+    /// If the command fails the error contains the reply of the server.
     pub fn command_simple(
         &'a self,
         command: Document,
@@ -347,13 +592,16 @@ impl<'a> Collection<'a> {
                 Err(error)   => return Err(error.into())
             }
         } else {
-            Err(error.into())
+            Err(ReplyError::from_reply(error, &reply))
         }
     }
 
     /// Execute a count query on the underlying collection.
     /// The `query` bson is not validated, simply passed along to the server. As such, compatibility and errors should be validated in the appropriate server documentation.
     ///
+    /// The count is estimated from collection metadata by the server and can be inaccurate, for example
+    /// after an unclean shutdown. Use `count_documents` for an accurate count.
+    ///
     /// For more information, see the [query reference](https://docs.mongodb.org/manual/reference/operator/query/) at the MongoDB website.
     pub fn count(
         &self,
@@ -364,7 +612,7 @@ impl<'a> Collection<'a> {
 
         let default_options = CountOptions::default();
         let options         = options.unwrap_or(&default_options);
-        let opts_bsonc      =  match options.opts {
+        let opts_bsonc      =  match options.count_opts() {
             Some(ref o) => Some(Bsonc::from_document(o)?),
             None => None
         };
@@ -396,6 +644,50 @@ impl<'a> Collection<'a> {
         }
     }
 
+    /// Count the documents matching `filter` with an aggregation, which gives an accurate count.
+    ///
+    /// The query flags in the options are not used.
+    pub fn count_documents(
+        &self,
+        filter:  &Document,
+        options: Option<&CountOptions>
+    ) -> Result<i64> {
+        assert!(!self.inner.is_null());
+
+        let default_options = CountOptions::default();
+        let options         = options.unwrap_or(&default_options);
+
+        let mut opts = options.count_opts().unwrap_or_default();
+        if options.skip > 0 {
+            opts.insert("skip", options.skip as i64);
+        }
+        if options.limit > 0 {
+            opts.insert("limit", options.limit as i64);
+        }
+        let opts_bsonc = Bsonc::from_document(&opts)?;
+
+        let mut error = BsoncError::empty();
+        let count = unsafe {
+            bindings::mongoc_collection_count_documents(
+                self.inner,
+                Bsonc::from_document(filter)?.inner(),
+                opts_bsonc.inner(),
+                match options.read_prefs {
+                    Some(ref prefs) => prefs.inner(),
+                    None => ptr::null()
+                },
+                ptr::null_mut(),
+                error.mut_inner()
+            )
+        };
+
+        if count < 0 {
+            Err(error.into())
+        } else {
+            Ok(count)
+        }
+    }
+
     /// Create a bulk operation. After creating call various functions such as `update`,
     /// `insert` and others. When calling `execute` these operations will be executed in
     /// batches.
@@ -415,10 +707,92 @@ impl<'a> Collection<'a> {
                 options.write_concern.inner()
             )
         };
+        if options.bypass_document_validation {
+            unsafe {
+                bindings::mongoc_bulk_operation_set_bypass_document_validation(inner, 1);
+            }
+        }
 
         BulkOperation::new(self, inner)
     }
 
+    /// Create an index on this collection and return its name.
+    ///
+    /// Options such as `unique` or `partialFilterExpression` can be set in the options of
+    /// the model. Creating an index that already exists with the same options succeeds.
+    pub fn create_index(&'a self, model: &IndexModel) -> Result<String> {
+        let command = doc! {
+            "createIndexes": self.get_name().into_owned(),
+            "indexes":       [model.as_document()]
+        };
+        self.command_simple(command, None)?;
+        Ok(model.name())
+    }
+
+    /// Drop the index with the given name from this collection.
+    /// Returns the server error if no such index exists.
+    pub fn drop_index(&'a self, name: &str) -> Result<()> {
+        let command = doc! {
+            "dropIndexes": self.get_name().into_owned(),
+            "index":       name
+        };
+        self.command_simple(command, None)?;
+        Ok(())
+    }
+
+    /// Drop all indexes from this collection, except for the index on `_id`.
+    pub fn drop_all_indexes(&'a self) -> Result<()> {
+        self.drop_index("*")
+    }
+
+    /// Get a cursor over the descriptions of the indexes on this collection.
+    pub fn list_indexes(&'a self) -> Result<Cursor<'a>> {
+        assert!(!self.inner.is_null());
+
+        let cursor_ptr = unsafe {
+            bindings::mongoc_collection_find_indexes_with_opts(
+                self.inner,
+                ptr::null()
+            )
+        };
+
+        if cursor_ptr.is_null() {
+            return Err(InvalidParamsError.into())
+        }
+
+        Ok(Cursor::new(
+            cursor::CreatedBy::Collection(self),
+            cursor_ptr,
+            None
+        ))
+    }
+
+    /// Get the distinct values of a field in the documents matching `query`.
+    /// Additional options such as a `collation` can be passed in `opts`, they are added to the `distinct` command.
+    ///
+    /// Returns an empty vector if no documents match.
+    pub fn distinct(
+        &'a self,
+        field: &str,
+        query: &Document,
+        opts:  Option<&Document>
+    ) -> Result<Vec<Bson>> {
+        let mut command = doc! {
+            "distinct": self.get_name().into_owned(),
+            "key":      field,
+            "query":    query.clone()
+        };
+        if let Some(opts) = opts {
+            command.extend(opts.clone());
+        }
+
+        let reply = self.command_simple(command, None)?;
+        match reply.get_array("values") {
+            Ok(values) => Ok(values.clone()),
+            Err(_) => Ok(Vec::new())
+        }
+    }
+
     /// Request that a collection be dropped, including all indexes associated with the collection.
     pub fn drop(&mut self) -> Result<()> {
         assert!(!self.inner.is_null());
@@ -437,9 +811,10 @@ impl<'a> Collection<'a> {
     }
 
     /// Execute a query on the underlying collection.
-    /// If no options are necessary, query can simply contain a query such as `{a:1}`.
-    /// If you would like to specify options such as a sort order, the query must be placed inside of `{"$query": {}}`
-    /// as specified by the server documentation. See the example below for how to properly specify additional options to query.
+    /// The query contains a filter such as `{a:1}`, set the `sort` of the options to specify a sort order.
+    /// For compatibility the query can also be placed inside of `{"$query": {}}` together with legacy modifiers such
+    /// as `$orderby`, `$hint`, `$comment`, `$max` and `$min`. These are passed to the server as the options of the
+    /// find command, options that are set explicitly take precedence.
     pub fn find(
         &'a self,
         query:   &Document,
@@ -449,22 +824,88 @@ impl<'a> Collection<'a> {
 
         let default_options = CommandAndFindOptions::default();
         let options         = options.unwrap_or(&default_options);
-        let fields_bsonc    = options.fields_bsonc();
+
+        self.find_with_fields(query, options, options.fields_bsonc())
+    }
+
+    /// Translates the options to the opts of `mongoc_collection_find_with_opts`. A query wrapped
+    /// in `$query` is unwrapped into the filter, with its modifiers such as `$orderby` as options.
+    fn find_with_fields(
+        &'a self,
+        query:        &Document,
+        options:      &CommandAndFindOptions,
+        fields_bsonc: Option<Bsonc>
+    ) -> Result<Cursor<'a>> {
+        assert!(!self.inner.is_null());
+
+        let mut opts = Document::new();
+        let filter = match query.get_document("$query") {
+            Ok(filter) => {
+                for (modifier, option) in QUERY_MODIFIERS {
+                    if let Some(value) = query.get(modifier) {
+                        opts.insert(*option, value.clone());
+                    }
+                }
+                filter.clone()
+            },
+            Err(_) => query.clone()
+        };
+        if options.skip > 0 {
+            opts.insert("skip", options.skip as i64);
+        }
+        if options.limit > 0 {
+            opts.insert("limit", options.limit as i64);
+        }
+        if options.batch_size > 0 {
+            opts.insert("batchSize", options.batch_size as i64);
+        }
+        if let Some(ref fields) = fields_bsonc {
+            opts.insert("projection", fields.as_document()?);
+        }
+        for (flag, option) in QUERY_FLAG_OPTIONS {
+            if options.query_flags.contains(flag) {
+                opts.insert(*option, true);
+            }
+        }
+        if let Some(ref collation) = options.collation {
+            opts.insert("collation", collation.clone());
+        }
+        if let Some(ref hint) = options.hint {
+            opts.insert("hint", hint.clone());
+        }
+        if let Some(ref sort) = options.sort {
+            opts.insert("sort", sort.clone());
+        }
+        if let Some(max_time) = options.max_time {
+            opts.insert("maxTimeMS", max_time.as_millis() as i64);
+        }
+        if let Some(ref comment) = options.comment {
+            opts.insert("comment", comment.clone());
+        }
+
+        let mut opts_bsonc = Bsonc::from_document(&opts)?;
+        if let Some(ref read_concern) = options.read_concern {
+            read_concern.append_to(&mut opts_bsonc)?;
+        }
+        if let Some(session) = options.session {
+            session.append_to(&mut opts_bsonc)?;
+        }
+
+        // Without opts the slave ok flag allowed reading from a secondary, which is
+        // what a secondary preferred read pref does.
+        let secondary_preferred = if options.read_prefs.is_none() && options.query_flags.contains(&QueryFlag::SlaveOk) {
+            Some(ReadPrefs::new(&ReadMode::SecondaryPreferred))
+        } else {
+            None
+        };
 
         let cursor_ptr = unsafe {
-            bindings::mongoc_collection_find(
+            bindings::mongoc_collection_find_with_opts(
                 self.inner,
-                options.query_flags.flags(),
-                options.skip,
-                options.limit,
-                options.batch_size,
-                Bsonc::from_document(query)?.inner(),
-                match fields_bsonc {
-                    Some(ref f) => f.inner(),
-                    None => ptr::null()
-                },
-                match options.read_prefs {
-                    Some(ref prefs) => prefs.inner(),
+                Bsonc::from_document(&filter)?.inner(),
+                opts_bsonc.inner(),
+                match options.read_prefs.as_ref().or(secondary_preferred.as_ref()) {
+                    Some(prefs) => prefs.inner(),
                     None => ptr::null()
                 }
             )
@@ -474,16 +915,62 @@ impl<'a> Collection<'a> {
             return Err(InvalidParamsError.into())
         }
 
-        Ok(Cursor::new(
+        let mut cursor = Cursor::new(
             cursor::CreatedBy::Collection(self),
             cursor_ptr,
             fields_bsonc
-        ))
+        );
+        cursor.set_utf8_lossy(options.utf8_lossy);
+        Ok(cursor)
+    }
+
+    /// Execute a query like `find` and deserialize every returned document into a `T`.
+    pub fn find_typed<T>(
+        &'a self,
+        query:   &Document,
+        options: Option<&CommandAndFindOptions>
+    ) -> Result<TypedCursor<'a, T>> where T: DeserializeOwned {
+        self.find(query, options).map(Cursor::deserialize)
+    }
+
+    /// Find documents near a point, nearest first.
+    ///
+    /// Builds a `$near` query with a GeoJSON point for the given field, optionally limited to a
+    /// maximum distance in meters. The field needs a `2dsphere` index, if there is none the server
+    /// error is returned when iterating the cursor. Returns an `InvalidParamsError` if the longitude
+    /// or latitude is out of range.
+    pub fn find_near(
+        &'a self,
+        field:               &str,
+        longitude:           f64,
+        latitude:            f64,
+        max_distance_meters: Option<f64>,
+        options:             Option<&CommandAndFindOptions>
+    ) -> Result<Cursor<'a>> {
+        if !(-180.0..=180.0).contains(&longitude) || !(-90.0..=90.0).contains(&latitude) {
+            return Err(InvalidParamsError.into())
+        }
+
+        let mut near = doc! {
+            "$geometry": {
+                "type": "Point",
+                "coordinates": [longitude, latitude]
+            }
+        };
+        if let Some(max_distance) = max_distance_meters {
+            near.insert("$maxDistance", max_distance);
+        }
+
+        let query = doc! {
+            field: { "$near": near }
+        };
+        self.find(&query, options)
     }
 
     /// Update and return an object.
     /// This is a thin wrapper around the findAndModify command. Pass in
     /// an operation that either updates, upserts or removes.
+    /// If the operation fails the error contains the reply of the server.
     pub fn find_and_modify(
         &'a self,
         query:     &Document,
@@ -503,6 +990,7 @@ impl<'a> Collection<'a> {
 
         // Do these before the mongoc call to make sure we keep
         // them around long enough.
+        let query_bsonc = Bsonc::from_document(&query)?;
         let sort_bsonc = match options.sort {
             Some(ref doc) => {
                 Some(Bsonc::from_document(doc)?)
@@ -515,35 +1003,45 @@ impl<'a> Collection<'a> {
             },
             FindAndModifyOperation::Remove => None
         };
+        let extra_bsonc = options.extra_bsonc()?;
+
+        let mut flags = match operation {
+            FindAndModifyOperation::Update(_) => bindings::MONGOC_FIND_AND_MODIFY_NONE,
+            FindAndModifyOperation::Upsert(_) => bindings::MONGOC_FIND_AND_MODIFY_UPSERT,
+            FindAndModifyOperation::Remove    => bindings::MONGOC_FIND_AND_MODIFY_REMOVE
+        };
+        if options.new {
+            flags |= bindings::MONGOC_FIND_AND_MODIFY_RETURN_NEW;
+        }
 
         let success = unsafe {
-            bindings::mongoc_collection_find_and_modify(
+            let opts = bindings::mongoc_find_and_modify_opts_new();
+            assert!(!opts.is_null());
+            if let Some(ref s) = sort_bsonc {
+                bindings::mongoc_find_and_modify_opts_set_sort(opts, s.inner());
+            }
+            if let Some(ref u) = update_bsonc {
+                bindings::mongoc_find_and_modify_opts_set_update(opts, u.inner());
+            }
+            if let Some(ref f) = fields_bsonc {
+                bindings::mongoc_find_and_modify_opts_set_fields(opts, f.inner());
+            }
+            bindings::mongoc_find_and_modify_opts_set_flags(opts, flags);
+            bindings::mongoc_find_and_modify_opts_set_bypass_document_validation(
+                opts,
+                options.bypass_document_validation as u8
+            );
+            bindings::mongoc_find_and_modify_opts_append(opts, extra_bsonc.inner());
+
+            let success = bindings::mongoc_collection_find_and_modify_with_opts(
                 self.inner,
-                Bsonc::from_document(&query)?.inner(),
-                match sort_bsonc {
-                    Some(ref s) => s.inner(),
-                    None => ptr::null()
-                },
-                match update_bsonc {
-                    Some(ref u) => u.inner(),
-                    None => ptr::null()
-                },
-                match fields_bsonc {
-                    Some(ref f) => f.inner(),
-                    None => ptr::null()
-                },
-                match operation {
-                    FindAndModifyOperation::Remove => true,
-                    _ => false
-                } as u8,
-                match operation {
-                    FindAndModifyOperation::Upsert(_) => true,
-                    _ => false
-                } as u8,
-                options.new as u8,
+                query_bsonc.inner(),
+                opts,
                 reply.mut_inner(),
                 error.mut_inner()
-            )
+            );
+            bindings::mongoc_find_and_modify_opts_destroy(opts);
+            success
         };
 
         if success == 1 {
@@ -552,7 +1050,69 @@ impl<'a> Collection<'a> {
                 Err(error)   => return Err(error.into())
             }
         } else {
-            Err(error.into())
+            Err(ReplyError::from_reply(error, &reply))
+        }
+    }
+
+    /// Update a single document matching the query and return it, none if no document
+    /// matched. Returns the document as it was before the update, or after the update if
+    /// `new` is set in the options.
+    ///
+    /// Returns an `InvalidParamsError` if the update document does not contain update
+    /// operators, use `find_one_and_replace` to replace a document.
+    pub fn find_one_and_update(
+        &'a self,
+        query:   &Document,
+        update:  &'a Document,
+        options: Option<&FindAndModifyOptions>
+    ) -> Result<Option<Document>> {
+        validate_update(update)?;
+        self.find_one_and_modify(query, FindAndModifyOperation::Update(update), options)
+    }
+
+    /// Replace a single document matching the query and return it, none if no document
+    /// matched. Returns the document as it was before the replacement, or the replacement
+    /// if `new` is set in the options.
+    ///
+    /// Returns an `InvalidParamsError` if the replacement contains update operators.
+    pub fn find_one_and_replace(
+        &'a self,
+        query:       &Document,
+        replacement: &'a Document,
+        options:     Option<&FindAndModifyOptions>
+    ) -> Result<Option<Document>> {
+        if replacement.keys().any(|key| key.starts_with('$')) {
+            return Err(InvalidParamsError.into())
+        }
+        self.find_one_and_modify(query, FindAndModifyOperation::Update(replacement), options)
+    }
+
+    /// Delete a single document matching the query and return it, none if no document
+    /// matched.
+    ///
+    /// Returns an `InvalidParamsError` if `new` is set in the options, there is no new
+    /// version of a deleted document.
+    pub fn find_one_and_delete(
+        &'a self,
+        query:   &Document,
+        options: Option<&FindAndModifyOptions>
+    ) -> Result<Option<Document>> {
+        if options.map(|options| options.new).unwrap_or(false) {
+            return Err(InvalidParamsError.into())
+        }
+        self.find_one_and_modify(query, FindAndModifyOperation::Remove, options)
+    }
+
+    fn find_one_and_modify(
+        &'a self,
+        query:     &Document,
+        operation: FindAndModifyOperation<'a>,
+        options:   Option<&FindAndModifyOptions>
+    ) -> Result<Option<Document>> {
+        let reply = self.find_and_modify(query, operation, options)?;
+        match reply.get("value") {
+            Some(&Bson::Document(ref document)) => Ok(Some(document.clone())),
+            _ => Ok(None)
         }
     }
 
@@ -564,26 +1124,116 @@ impl<'a> Collection<'a> {
         String::from_utf8_lossy(cstr.to_bytes())
     }
 
-    /// Insert document into collection.
-    /// If no `_id` element is found in document, then an id will be generated locally and added to the document.
-    // TODO: You can retrieve a generated _id from mongoc_collection_get_last_error().
-    pub fn insert(
-        &'a self,
-        document: &Document,
-        options:  Option<&InsertOptions>
-    ) -> Result<()> {
+    /// Get the write concern of this collection.
+    pub fn get_write_concern(&self) -> WriteConcern {
+        assert!(!self.inner.is_null());
+        WriteConcern::from_copy(unsafe { bindings::mongoc_collection_get_write_concern(self.inner) })
+    }
+
+    /// Set the write concern of this collection, which is used for writes that don't set
+    /// their own write concern.
+    pub fn set_write_concern(&mut self, write_concern: &WriteConcern) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_collection_set_write_concern(self.inner, write_concern.inner());
+        }
+    }
+
+    /// Get the read concern of this collection.
+    pub fn get_read_concern(&self) -> ReadConcern {
+        assert!(!self.inner.is_null());
+        ReadConcern::from_copy(unsafe { bindings::mongoc_collection_get_read_concern(self.inner) })
+    }
+
+    /// Set the read concern of this collection, which is used for reads that don't set
+    /// their own read concern.
+    pub fn set_read_concern(&mut self, read_concern: &ReadConcern) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_collection_set_read_concern(self.inner, read_concern.inner());
+        }
+    }
+
+    /// Get the read prefs of this collection.
+    pub fn get_read_prefs(&self) -> ReadPrefs {
+        assert!(!self.inner.is_null());
+        ReadPrefs::from_copy(unsafe { bindings::mongoc_collection_get_read_prefs(self.inner) })
+    }
+
+    /// Set the read prefs of this collection, which are used for reads that don't set
+    /// their own read prefs.
+    pub fn set_read_prefs(&mut self, read_prefs: &ReadPrefs) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_collection_set_read_prefs(self.inner, read_prefs.inner());
+        }
+    }
+
+    /// Insert document into collection.
+    /// If no `_id` element is found in document, then an id will be generated locally and added to the document.
+    ///
+    /// Kept for compatibility, use `insert_one` to get the id of the inserted document.
+    #[deprecated(note = "use insert_one")]
+    pub fn insert(
+        &'a self,
+        document: &Document,
+        options:  Option<&InsertOptions>
+    ) -> Result<()> {
+        self.insert_document(document, options)
+    }
+
+    /// Insert document into collection and return the `_id` of the inserted document.
+    ///
+    /// If no `_id` element is found in document, then an `ObjectId` is generated locally
+    /// and inserted as the `_id`, the result contains that same id.
+    pub fn insert_one(
+        &'a self,
+        document: &Document,
+        options:  Option<&InsertOptions>
+    ) -> Result<InsertOneResult> {
+        if let Some(id) = document.get("_id") {
+            self.insert_document(document, options)?;
+            return Ok(InsertOneResult { inserted_id: id.clone() })
+        }
+
+        let id = Bson::ObjectId(ObjectId::new());
+        let mut document_with_id = doc! { "_id": id.clone() };
+        document_with_id.extend(document.clone());
+        self.insert_document(&document_with_id, options)?;
+        Ok(InsertOneResult { inserted_id: id })
+    }
+
+    fn insert_document(
+        &self,
+        document: &Document,
+        options:  Option<&InsertOptions>
+    ) -> Result<()> {
         assert!(!self.inner.is_null());
 
         let default_options = InsertOptions::default();
         let options         = options.unwrap_or(&default_options);
 
+        let mut opts = Document::new();
+        if options.insert_flags.contains(&InsertFlag::NoValidate) {
+            opts.insert("validate", false);
+        }
+        if options.bypass_document_validation {
+            opts.insert("bypassDocumentValidation", true);
+        }
+        let mut opts_bsonc = Bsonc::from_document(&opts)?;
+        options.write_concern.append_to(&mut opts_bsonc)?;
+        if let Some(session) = options.session {
+            session.append_to(&mut opts_bsonc)?;
+        }
+
+        let mut reply = Bsonc::new();
         let mut error = BsoncError::empty();
         let success = unsafe {
-            bindings::mongoc_collection_insert(
+            bindings::mongoc_collection_insert_one(
                 self.inner,
-                options.insert_flags.flags(),
                 Bsonc::from_document(&document)?.inner(),
-                options.write_concern.inner(),
+                opts_bsonc.inner(),
+                reply.mut_inner(),
                 error.mut_inner()
             )
         };
@@ -591,8 +1241,69 @@ impl<'a> Collection<'a> {
         if success == 1 {
             Ok(())
         } else {
-            Err(error.into())
+            Err(ReplyError::from_reply(error, &reply))
+        }
+    }
+
+    /// Serialize a value to a document and insert it like `insert`. The value must serialize
+    /// to a document, such as a struct or a map.
+    pub fn insert_typed<T>(
+        &'a self,
+        value:   &T,
+        options: Option<&InsertOptions>
+    ) -> Result<()> where T: Serialize {
+        let document = bson::to_document(value)?;
+        self.insert_document(&document, options)
+    }
+
+    /// Insert document into collection and return its `_id`, like `insert_one`.
+    pub fn insert_returning_id(
+        &'a self,
+        document: &Document,
+        options:  Option<&InsertOptions>
+    ) -> Result<Bson> {
+        self.insert_one(document, options).map(|result| result.inserted_id)
+    }
+
+    /// Insert multiple documents into the collection with a bulk operation.
+    ///
+    /// Returns the summary reply of the bulk operation, containing `nInserted`. When an insert
+    /// fails the error is a `MongoError::Reply` that includes the summary reply with the
+    /// `writeErrors`. Returns an `InvalidParamsError` if there are no documents to insert.
+    pub fn insert_many(
+        &'a self,
+        documents: &[Document],
+        options:   Option<&InsertManyOptions>
+    ) -> Result<Document> {
+        assert!(!self.inner.is_null());
+
+        if documents.is_empty() {
+            return Err(InvalidParamsError.into())
         }
+
+        let default_options = InsertManyOptions::default();
+        let options         = options.unwrap_or(&default_options);
+
+        let inner = unsafe {
+            bindings::mongoc_collection_create_bulk_operation(
+                self.inner,
+                options.ordered as u8,
+                options.write_concern.inner()
+            )
+        };
+        let bulk_operation = BulkOperation::new(self, inner);
+        for document in documents {
+            bulk_operation.insert(document)?;
+        }
+        bulk_operation.execute().map_err(|error| match error.error {
+            MongoError::Bsonc(bsonc_error) if !error.reply.is_empty() => {
+                MongoError::Reply(Box::new(ReplyError {
+                    error: bsonc_error,
+                    reply: error.reply
+                }))
+            },
+            other => other
+        })
     }
 
     /// Remove documents in the given collection that match selector.
@@ -626,6 +1337,39 @@ impl<'a> Collection<'a> {
         }
     }
 
+    /// Rename this collection, possibly moving it to another database. If `drop_target` is true
+    /// an existing collection with the new name is dropped first, otherwise the rename fails.
+    ///
+    /// On success this collection refers to the renamed collection.
+    pub fn rename(
+        &mut self,
+        new_db:      &str,
+        new_name:    &str,
+        drop_target: bool
+    ) -> Result<()> {
+        assert!(!self.inner.is_null());
+
+        let new_db_cstring   = CString::new(new_db)?;
+        let new_name_cstring = CString::new(new_name)?;
+        let mut error = BsoncError::empty();
+
+        let success = unsafe {
+            bindings::mongoc_collection_rename(
+                self.inner,
+                new_db_cstring.as_ptr(),
+                new_name_cstring.as_ptr(),
+                drop_target as u8,
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
     /// Save a document into the collection. If the document has an `_id` field it will be updated.
     /// Otherwise it will be inserted.
     pub fn save(
@@ -657,33 +1401,133 @@ impl<'a> Collection<'a> {
 
     /// This function updates documents in collection that match selector.
     /// By default, updates only a single document. Add `MultiUpdate` flag to update multiple documents.
+    /// If the update document does not contain any update operators the matching document will be
+    /// replaced, in that case `MultiUpdate` is not supported.
+    ///
+    /// Returns an `InvalidParamsError` if the update document is empty, or if it is a
+    /// replacement and the `MultiUpdate` flag is set.
     pub fn update(
         &self,
         selector: &Document,
         update:   &Document,
         options:  Option<&UpdateOptions>
     ) -> Result<()> {
-        assert!(!self.inner.is_null());
+        let default_options = UpdateOptions::default();
+        let options         = options.unwrap_or(&default_options);
+
+        if update.is_empty() {
+            return Err(InvalidParamsError.into())
+        }
+        let is_replacement = validate_update(update).is_err();
+        let update_fn = if is_replacement {
+            if options.update_flags.contains(&UpdateFlag::MultiUpdate) {
+                return Err(InvalidParamsError.into())
+            }
+            bindings::mongoc_collection_replace_one
+        } else if options.update_flags.contains(&UpdateFlag::MultiUpdate) {
+            bindings::mongoc_collection_update_many
+        } else {
+            bindings::mongoc_collection_update_one
+        };
+
+        self.update_with(update_fn, selector, update, options.opts(), &options.write_concern)?;
+        Ok(())
+    }
+
+    /// Updates a single document in the collection that matches selector. The update document must
+    /// only contain update operators. The `MultiUpdate` flag in the options is ignored.
+    pub fn update_one(
+        &self,
+        selector: &Document,
+        update:   &Document,
+        options:  Option<&UpdateOptions>
+    ) -> Result<UpdateResult> {
+        let default_options = UpdateOptions::default();
+        let options         = options.unwrap_or(&default_options);
+        self.update_with(
+            bindings::mongoc_collection_update_one,
+            selector,
+            update,
+            options.opts(),
+            &options.write_concern
+        )
+    }
 
+    /// Updates all documents in the collection that match selector. The update document must
+    /// only contain update operators. The `MultiUpdate` flag in the options is not needed.
+    pub fn update_many(
+        &self,
+        selector: &Document,
+        update:   &Document,
+        options:  Option<&UpdateOptions>
+    ) -> Result<UpdateResult> {
         let default_options = UpdateOptions::default();
         let options         = options.unwrap_or(&default_options);
+        self.update_with(
+            bindings::mongoc_collection_update_many,
+            selector,
+            update,
+            options.opts(),
+            &options.write_concern
+        )
+    }
+
+    /// Replaces a single document in the collection that matches selector. The replacement
+    /// document must not contain update operators.
+    pub fn replace_one(
+        &self,
+        selector:    &Document,
+        replacement: &Document,
+        options:     Option<&ReplaceOptions>
+    ) -> Result<UpdateResult> {
+        let default_options = ReplaceOptions::default();
+        let options         = options.unwrap_or(&default_options);
+        self.update_with(
+            bindings::mongoc_collection_replace_one,
+            selector,
+            replacement,
+            options.opts(),
+            &options.write_concern
+        )
+    }
 
+    fn update_with(
+        &self,
+        update_fn:     unsafe extern "C" fn(
+            *mut bindings::mongoc_collection_t,
+            *const bindings::bson_t,
+            *const bindings::bson_t,
+            *const bindings::bson_t,
+            *mut bindings::bson_t,
+            *mut bindings::bson_error_t
+        ) -> u8,
+        selector:      &Document,
+        update:        &Document,
+        opts:          Document,
+        write_concern: &WriteConcern
+    ) -> Result<UpdateResult> {
+        assert!(!self.inner.is_null());
+
+        let mut opts_bsonc = Bsonc::from_document(&opts)?;
+        write_concern.append_to(&mut opts_bsonc)?;
+
+        let mut reply = Bsonc::new();
         let mut error = BsoncError::empty();
         let success = unsafe {
-            bindings::mongoc_collection_update(
+            update_fn(
                 self.inner,
-                options.update_flags.flags(),
                 Bsonc::from_document(&selector)?.inner(),
                 Bsonc::from_document(&update)?.inner(),
-                options.write_concern.inner(),
+                opts_bsonc.inner(),
+                reply.mut_inner(),
                 error.mut_inner()
             )
         };
 
         if success == 1 {
-            Ok(())
+            Ok(UpdateResult::from_reply(&reply.as_document()?))
         } else {
-            Err(error.into())
+            Err(ReplyError::from_reply(error, &reply))
         }
     }
 
@@ -703,7 +1547,7 @@ impl<'a> Collection<'a> {
     pub fn tail(
         &'a self,
         query:        Document,
-        find_options: Option<CommandAndFindOptions>,
+        find_options: Option<CommandAndFindOptions<'a>>,
         tail_options: Option<TailOptions>
     ) -> TailingCursor<'a> {
         TailingCursor::new(
@@ -713,6 +1557,88 @@ impl<'a> Collection<'a> {
             tail_options.unwrap_or(TailOptions::default())
         )
     }
+
+    /// Watch this collection for changes. The pipeline contains aggregation stages, such
+    /// as `$match`, that filter or transform the change events on the server.
+    ///
+    /// ```no_run
+    /// # #[macro_use] extern crate bson;
+    /// # extern crate mongo_driver;
+    /// # use mongo_driver::client::{ClientPool,Uri};
+    /// # fn main() {
+    /// # let uri = Uri::new("mongodb://localhost:27017/").unwrap();
+    /// # let pool = ClientPool::new(uri, None);
+    /// # let client = pool.pop();
+    /// # let collection = client.get_collection("db", "collection");
+    /// let pipeline = vec![doc! { "$match": { "operationType": "insert" } }];
+    /// let change_stream = collection.watch(&pipeline, None).unwrap();
+    /// # }
+    /// ```
+    pub fn watch(
+        &'a self,
+        pipeline: &[Document],
+        options:  Option<&ChangeStreamOptions>
+    ) -> Result<ChangeStream<'a>> {
+        assert!(!self.inner.is_null());
+        ChangeStream::watch(
+            change_stream::CreatedBy::Collection(self),
+            pipeline,
+            options,
+            |pipeline, opts| unsafe { bindings::mongoc_collection_watch(self.inner, pipeline, opts) }
+        )
+    }
+
+    /// Execute a text search on the collection.
+    ///
+    /// Builds a `$text` query for the search string and merges in the optional filter. Searching
+    /// requires a text index on the collection, if there is none the server error is returned when
+    /// iterating the cursor. Set a `score_field` in the options to project the text score.
+    pub fn text_search(
+        &'a self,
+        search:  &str,
+        filter:  Option<&Document>,
+        options: Option<&TextSearchOptions>
+    ) -> Result<Cursor<'a>> {
+        let default_options = TextSearchOptions::default();
+        let options         = options.unwrap_or(&default_options);
+
+        let mut query = doc! {
+            "$text": { "$search": search }
+        };
+        if let Some(filter) = filter {
+            query.extend(filter.clone());
+        }
+
+        let fields = match options.score_field {
+            Some(ref score_field) => {
+                let mut fields = options.find_options.fields.clone().unwrap_or_default();
+                fields.insert(score_field.to_owned(), doc! { "$meta": "textScore" });
+                Some(fields)
+            },
+            None => options.find_options.fields.clone()
+        };
+        let fields_bsonc = match fields {
+            Some(ref f) => Some(Bsonc::from_document(f)?),
+            None => None
+        };
+
+        self.find_with_fields(&query, &options.find_options, fields_bsonc)
+    }
+}
+
+/// Check that an update document contains update operators, so it is not mistaken for a
+/// replacement. An aggregation pipeline, a document with the keys `0`, `1` and so on, is
+/// passed through as well.
+fn validate_update(document: &Document) -> Result<()> {
+    let has_operator = document.keys().any(|key| key.starts_with('$'));
+    let is_pipeline  = !document.is_empty() && document.iter().enumerate().all(|(i, (key, value))| {
+        *key == i.to_string() && value.as_document().is_some()
+    });
+    if has_operator || is_pipeline {
+        Ok(())
+    } else {
+        Err(InvalidParamsError.into())
+    }
 }
 
 impl<'a> Drop for Collection<'a> {
@@ -818,8 +1744,8 @@ impl<'a>BulkOperation<'a> {
     /// Queue update of a single documents into a bulk operation.
     /// The update is not performed until `execute` is called.
     ///
-    /// TODO: document must only contain fields whose key starts
-    /// with $, these is no error handling for this.
+    /// The document must contain update operators or be an aggregation pipeline,
+    /// otherwise an `InvalidParamsError` is returned.
     pub fn update_one(
         &self,
         selector: &Document,
@@ -827,6 +1753,7 @@ impl<'a>BulkOperation<'a> {
         upsert:   bool
     ) -> Result<()> {
         assert!(!self.inner.is_null());
+        validate_update(document)?;
         unsafe {
             bindings::mongoc_bulk_operation_update_one(
                 self.inner,
@@ -841,8 +1768,8 @@ impl<'a>BulkOperation<'a> {
     /// Queue update of multiple documents into a bulk operation.
     /// The update is not performed until `execute` is called.
     ///
-    /// TODO: document must only contain fields whose key starts
-    /// with $, these is no error handling for this.
+    /// The document must contain update operators or be an aggregation pipeline,
+    /// otherwise an `InvalidParamsError` is returned.
     pub fn update(
         &self,
         selector: &Document,
@@ -850,6 +1777,7 @@ impl<'a>BulkOperation<'a> {
         upsert:   bool
     ) -> Result<()> {
         assert!(!self.inner.is_null());
+        validate_update(document)?;
         unsafe {
             bindings::mongoc_bulk_operation_update(
                 self.inner,
@@ -861,6 +1789,141 @@ impl<'a>BulkOperation<'a> {
         Ok(())
     }
 
+    /// Queue removal of all documents matching the provided selector into a bulk operation,
+    /// with options such as `collation`. The removal is not performed until `execute` is called.
+    pub fn remove_with_opts(
+        &self,
+        selector: &Document,
+        opts:     &Document
+    ) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_bulk_operation_remove_many_with_opts(
+                self.inner,
+                Bsonc::from_document(&selector)?.inner(),
+                Bsonc::from_document(&opts)?.inner(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Queue removal of a single document into a bulk operation, with options such as
+    /// `collation`. The removal is not performed until `execute` is called.
+    pub fn remove_one_with_opts(
+        &self,
+        selector: &Document,
+        opts:     &Document
+    ) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_bulk_operation_remove_one_with_opts(
+                self.inner,
+                Bsonc::from_document(&selector)?.inner(),
+                Bsonc::from_document(&opts)?.inner(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Queue replacement of a single document into a bulk operation, with options such as
+    /// `collation` and `upsert`. The replacement is not performed until `execute` is called.
+    pub fn replace_one_with_opts(
+        &self,
+        selector: &Document,
+        document: &Document,
+        opts:     &Document
+    ) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_bulk_operation_replace_one_with_opts(
+                self.inner,
+                Bsonc::from_document(&selector)?.inner(),
+                Bsonc::from_document(&document)?.inner(),
+                Bsonc::from_document(&opts)?.inner(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Queue update of a single document into a bulk operation, with options such as
+    /// `collation`, `upsert` and `arrayFilters`. The update is not performed until `execute` is called.
+    /// The document must contain update operators or be an aggregation pipeline.
+    pub fn update_one_with_opts(
+        &self,
+        selector: &Document,
+        document: &Document,
+        opts:     &Document
+    ) -> Result<()> {
+        assert!(!self.inner.is_null());
+        validate_update(document)?;
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_bulk_operation_update_one_with_opts(
+                self.inner,
+                Bsonc::from_document(&selector)?.inner(),
+                Bsonc::from_document(&document)?.inner(),
+                Bsonc::from_document(&opts)?.inner(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Queue update of multiple documents into a bulk operation, with options such as
+    /// `collation`, `upsert` and `arrayFilters`. The update is not performed until `execute` is called.
+    /// The document must contain update operators or be an aggregation pipeline.
+    pub fn update_with_opts(
+        &self,
+        selector: &Document,
+        document: &Document,
+        opts:     &Document
+    ) -> Result<()> {
+        assert!(!self.inner.is_null());
+        validate_update(document)?;
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_bulk_operation_update_many_with_opts(
+                self.inner,
+                Bsonc::from_document(&selector)?.inner(),
+                Bsonc::from_document(&document)?.inner(),
+                Bsonc::from_document(&opts)?.inner(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
     /// This function executes all operations queued into this bulk operation.
     /// If ordered was set true, forward progress will be stopped upon the first error.
     ///
@@ -886,13 +1949,13 @@ impl<'a>BulkOperation<'a> {
 
         let document = match reply.as_document() {
             Ok(document) => document,
-            Err(error)   => return Err(BulkOperationError{error: error.into(), reply: doc!{}})
+            Err(error)   => return Err(BulkOperationError::new(error.into(), doc!{}))
         };
 
         if return_value != 0 {
             Ok(document)
         } else {
-            Err(BulkOperationError{error: error.into(), reply: document})
+            Err(BulkOperationError::new(error.into(), document))
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Access to a MongoDB query cursor.
 
 use std::iter::Iterator;
+use std::marker::PhantomData;
 use std::ptr;
 use std::thread;
 use std::time::Duration;
@@ -8,11 +9,14 @@ use std::collections::VecDeque;
 
 use crate::mongoc::bindings;
 use bson::{self,Bson,Document,oid};
+use serde::de::DeserializeOwned;
 
-use super::BsoncError;
+use super::{BsoncError,InvalidParamsError,MongoError};
 use super::bsonc;
 use super::client::Client;
 use super::database::Database;
+use super::gridfs::GridFs;
+use super::read_prefs::ReadPrefs;
 use super::flags::QueryFlag;
 use super::collection::{Collection,TailOptions};
 use super::CommandAndFindOptions;
@@ -21,10 +25,12 @@ use super::MongoError::ValueAccessError;
 use super::Result;
 
 #[doc(hidden)]
+#[derive(Clone,Copy)]
 pub enum CreatedBy<'a> {
     Client(&'a Client<'a>),
     Database(&'a Database<'a>),
-    Collection(&'a Collection<'a>)
+    Collection(&'a Collection<'a>),
+    GridFs(&'a GridFs<'a>)
 }
 
 /// Provides access to a MongoDB cursor for a normal operation.
@@ -36,11 +42,16 @@ pub enum CreatedBy<'a> {
 ///
 /// `Cursor` implements the `Iterator` trait, so you can use with all normal Rust means
 /// of iteration and looping.
+///
+/// A cursor does not collect its results. It holds the current batch returned by the server
+/// and converts one document at a time while iterating, so memory usage is bounded by the
+/// batch size. Use `into_bounded_iter` to limit the size of a batch.
 pub struct Cursor<'a> {
     _created_by:        CreatedBy<'a>,
     inner:              *mut bindings::mongoc_cursor_t,
     tailing:            bool,
     tail_wait_duration: Duration,
+    utf8_lossy:         bool,
     // Become owner of bsonc because the cursor needs it
     // to be allocated for it's entire lifetime
     _fields:            Option<bsonc::Bsonc>
@@ -59,10 +70,125 @@ impl<'a> Cursor<'a> {
             inner:              inner,
             tailing:            false,
             tail_wait_duration: Duration::from_millis(0),
+            utf8_lossy:         true,
             _fields:            fields
         }
     }
 
+    /// Create a new cursor that runs the same query again from the start, for example to retry
+    /// after a transient error. The new cursor is independent of this one.
+    pub fn clone_cursor(&self) -> Cursor<'a> {
+        assert!(!self.inner.is_null());
+        let inner = unsafe { bindings::mongoc_cursor_clone(self.inner) };
+        assert!(!inner.is_null());
+        Cursor {
+            _created_by:        self._created_by,
+            inner:              inner,
+            tailing:            self.tailing,
+            tail_wait_duration: self.tail_wait_duration,
+            utf8_lossy:         self.utf8_lossy,
+            _fields:            self._fields.clone()
+        }
+    }
+
+    /// Set the number of documents to fetch from the server in each batch.
+    ///
+    /// This must be called before the first call to `next`, mongoc ignores changes once
+    /// iteration has started.
+    pub fn set_batch_size(&mut self, batch_size: u32) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_cursor_set_batch_size(self.inner, batch_size);
+        }
+    }
+
+    /// Iterate while holding at most `prefetch` documents of a batch in memory, by fetching
+    /// batches of that size from the server. A prefetch of zero is treated as one.
+    ///
+    /// This must be called before the first call to `next`, like `set_batch_size`.
+    pub fn into_bounded_iter(mut self, prefetch: usize) -> Cursor<'a> {
+        let batch_size = prefetch.max(1).min(u32::MAX as usize) as u32;
+        self.set_batch_size(batch_size);
+        self
+    }
+
+    /// Collect all documents, stopping at the first error. The documents that were
+    /// returned before the error are dropped.
+    pub fn try_collect(self) -> Result<Vec<Document>> {
+        let mut documents = Vec::new();
+        for result in self {
+            documents.push(result?);
+        }
+        Ok(documents)
+    }
+
+    /// Deserialize every document this cursor returns into a `T`, a document that cannot be
+    /// deserialized results in a `MongoError::Decoder` for that item.
+    pub fn deserialize<T>(self) -> TypedCursor<'a, T> where T: DeserializeOwned {
+        TypedCursor::new(self)
+    }
+
+    /// Set whether invalid UTF-8 in strings is replaced with the replacement character, which
+    /// is the default, or results in a `MongoError::Decoder` for the document.
+    pub fn set_utf8_lossy(&mut self, utf8_lossy: bool) {
+        self.utf8_lossy = utf8_lossy;
+    }
+
+    /// Number of documents fetched in each batch, zero if the server default is used.
+    pub fn batch_size(&self) -> u32 {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_cursor_get_batch_size(self.inner)
+        }
+    }
+
+    /// Set how long the server blocks waiting for new results on a tailable cursor with
+    /// the `AwaitData` flag, before returning an empty batch.
+    pub fn set_max_await_time(&mut self, max_await_time: Duration) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_cursor_set_max_await_time_ms(self.inner, max_await_time.as_millis() as u32);
+        }
+    }
+
+    /// How long the server blocks waiting for new results, zero if the server default is used.
+    pub fn max_await_time(&self) -> Duration {
+        assert!(!self.inner.is_null());
+        let max_await_time_ms = unsafe { bindings::mongoc_cursor_get_max_await_time_ms(self.inner) };
+        Duration::from_millis(max_await_time_ms as u64)
+    }
+
+    /// Id of the cursor on the server, zero if the server has no more results or
+    /// before the first call to `next`.
+    pub fn cursor_id(&self) -> i64 {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_cursor_get_id(self.inner)
+        }
+    }
+
+    /// Id of the server this cursor reads from, zero before the first call to `next`.
+    /// A `getMore` for this cursor must be sent to the same server.
+    pub fn server_id(&self) -> u32 {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_cursor_get_hint(self.inner)
+        }
+    }
+
+    /// Send this cursor to the server with the given id, for example to run a `getMore` on the
+    /// server that returned the cursor. Must be called before the first call to `next`.
+    ///
+    /// Returns an `InvalidParamsError` if the server id is zero or was already set.
+    pub fn set_server_id(&mut self, server_id: u32) -> Result<()> {
+        assert!(!self.inner.is_null());
+        if unsafe { bindings::mongoc_cursor_set_hint(self.inner, server_id) } == 1 {
+            Ok(())
+        } else {
+            Err(InvalidParamsError.into())
+        }
+    }
+
     fn is_alive(&self) -> bool {
         assert!(!self.inner.is_null());
         unsafe {
@@ -136,7 +262,12 @@ impl<'a> Iterator for Cursor<'a> {
 
             // Parse and return bson document.
             let bsonc = bsonc::Bsonc::from_ptr(bson_ptr);
-            match bsonc.as_document() {
+            let document = if self.utf8_lossy {
+                bsonc.as_document()
+            } else {
+                bsonc.as_document_strict()
+            };
+            match document {
                 Ok(document) => return Some(Ok(document)),
                 Err(error)   => return Some(Err(error.into()))
             }
@@ -153,6 +284,42 @@ impl<'a> Drop for Cursor<'a> {
     }
 }
 
+/// Cursor that deserializes every document it returns into a `T`.
+///
+/// A `TypedCursor` is obtained by calling `find_typed` on a `Collection` or `deserialize` on
+/// a `Cursor`. A document that
+/// cannot be deserialized results in a `MongoError::Decoder` for that item, iteration
+/// can continue after it.
+pub struct TypedCursor<'a, T> {
+    cursor:  Cursor<'a>,
+    _marker: PhantomData<T>
+}
+
+impl<'a, T> TypedCursor<'a, T> where T: DeserializeOwned {
+    #[doc(hidden)]
+    pub fn new(cursor: Cursor<'a>) -> TypedCursor<'a, T> {
+        TypedCursor {
+            cursor:  cursor,
+            _marker: PhantomData
+        }
+    }
+
+    /// Get the underlying cursor, which returns the documents without deserializing them.
+    pub fn into_inner(self) -> Cursor<'a> {
+        self.cursor
+    }
+}
+
+impl<'a, T> Iterator for TypedCursor<'a, T> where T: DeserializeOwned {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|result| {
+            result.and_then(|document| bson::from_document(document).map_err(|err| err.into()))
+        })
+    }
+}
+
 /// Cursor that will reconnect and resume tailing a collection
 /// at the right point if the connection fails.
 ///
@@ -162,7 +329,7 @@ impl<'a> Drop for Cursor<'a> {
 pub struct TailingCursor<'a> {
     collection:   &'a Collection<'a>,
     query:        Document,
-    find_options: CommandAndFindOptions,
+    find_options: CommandAndFindOptions<'a>,
     tail_options: TailOptions,
     cursor:       Option<Cursor<'a>>,
     last_seen_id: Option<oid::ObjectId>,
@@ -174,7 +341,7 @@ impl<'a> TailingCursor<'a> {
     pub fn new(
         collection:   &'a Collection<'a>,
         query:        Document,
-        find_options: CommandAndFindOptions,
+        find_options: CommandAndFindOptions<'a>,
         tail_options: TailOptions
     ) -> TailingCursor<'a> {
         // Add flags to make query tailable
@@ -215,6 +382,9 @@ impl<'a> Iterator for TailingCursor<'a> {
                         Ok(mut c)  => {
                             c.tailing            = true;
                             c.tail_wait_duration = self.tail_options.wait_duration;
+                            if let Some(max_await_time) = self.tail_options.max_await_time {
+                                c.set_max_await_time(max_await_time);
+                            }
                             Some(c)
                         },
                         Err(e) => return Some(Err(e.into()))
@@ -266,10 +436,17 @@ type CursorId = i64;
 /// allows you to have much cleaner user code.  Only commands which
 /// return batches work with this cursor.  For example, find, aggregate,
 /// and listIndexes all return batches.
+///
+/// The `getMore` commands use the read prefs of the initial command and are sent to
+/// the server that returned the cursor, a cursor only exists on that server. If a batch
+/// size is set every `getMore` requests batches of that size.
 pub struct BatchCursor<'a> {
     cursor:     Cursor<'a>,
     db:         &'a Database<'a>,
     coll_name:  String,
+    read_prefs: Option<ReadPrefs>,
+    batch_size: u32,
+    server_id:  u32,
     cursor_id:  Option<CursorId>,
     documents:  Option<DocArray>
 }
@@ -278,12 +455,17 @@ impl<'a> BatchCursor<'a> {
     pub fn new(
         cursor: Cursor<'a>,
         db: &'a Database<'a>,
-        coll_name: String
+        coll_name: String,
+        read_prefs: Option<ReadPrefs>,
+        batch_size: u32
     ) -> BatchCursor<'a> {
         BatchCursor {
             cursor,
             db,
             coll_name,
+            read_prefs,
+            batch_size,
+            server_id: 0,
             cursor_id: None,
             documents: None
         }
@@ -293,20 +475,34 @@ impl<'a> BatchCursor<'a> {
     // and store them in the DocArray buffer
     fn get_cursor_next(&mut self) -> Option<Result<Document>> {
         let item_opt = self.cursor.next();
-        if let Some(item_res) = item_opt {
-            if let Ok(item) = item_res {
-                let docs_ret = batch_to_array(item);
-                if let Ok(docs) = docs_ret {
-                    self.documents = docs.0;
-                    if docs.1.is_some() {self.cursor_id = docs.1}
-                    let res = self.get_next_doc();
-                    if res.is_some() { return res; }
-                } else {
-                    return Some(Err(docs_ret.err().unwrap()));
+        if self.server_id == 0 {
+            self.server_id = self.cursor.server_id();
+        }
+        let docs_ret = match item_opt? {
+            Ok(item) => batch_to_array(item),
+            Err(err) => Err(err)
+        };
+        match docs_ret {
+            Ok(docs) => {
+                self.documents = docs.0;
+                // A cursor id of zero means the server has no more results
+                match docs.1 {
+                    Some(0) => self.cursor_id = None,
+                    Some(id) => self.cursor_id = Some(id),
+                    None => ()
                 }
-            }
+                self.get_next_doc()
+            },
+            Err(err) => Some(Err(self.fail(err)))
         }
-        None
+    }
+
+    // internal function to end iteration after an error, so no more getMore
+    // commands are sent for a cursor that failed
+    fn fail(&mut self, err: MongoError) -> MongoError {
+        self.cursor_id = None;
+        self.documents = None;
+        err
     }
 
     // internal function for pulling the next document from the documents buffer.
@@ -363,16 +559,26 @@ impl<'a> Iterator for BatchCursor<'a> {
 
         // (3) try getMore
         if let Some(cid) = self.cursor_id {
-            let command = doc! {
+            let mut command = doc! {
                 "getMore": cid as i64,
                 "collection": self.coll_name.clone()
                 };
-            let cur_result = self.db.command(command, None);
-            if let Ok(cur) = cur_result {
-                self.cursor = cur;
-                let res = self.get_cursor_next();
-                if res.is_some() { return res; }
+            if self.batch_size > 0 {
+                command.insert("batchSize", self.batch_size as i64);
+            }
+            let mut options = CommandAndFindOptions::default();
+            options.read_prefs = self.read_prefs.clone();
+            let mut cur = match self.db.command(command, Some(&options)) {
+                Ok(cur) => cur,
+                Err(err) => return Some(Err(self.fail(err)))
+            };
+            if self.server_id != 0 {
+                if let Err(err) = cur.set_server_id(self.server_id) {
+                    return Some(Err(self.fail(err)))
+                }
             }
+            self.cursor = cur;
+            return self.get_cursor_next();
         }
         None
     }
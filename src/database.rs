@@ -6,18 +6,24 @@ use std::ptr;
 
 use crate::mongoc::bindings;
 use bson::Document;
+use serde::de::DeserializeOwned;
 
 use super::Result;
 use super::CommandAndFindOptions;
-use super::{BsoncError,InvalidParamsError};
+use super::take_strv;
+use super::{BsoncError,InvalidParamsError,ReplyError};
 use super::bsonc::Bsonc;
+use super::change_stream;
+use super::change_stream::{ChangeStream,ChangeStreamOptions};
 use super::client::Client;
 use super::collection;
 use super::collection::Collection;
 use super::cursor;
 use super::cursor::Cursor;
 use super::cursor::BatchCursor;
+use super::read_concern::ReadConcern;
 use super::read_prefs::ReadPrefs;
+use super::write_concern::WriteConcern;
 use crate::flags::FlagsValue;
 
 #[doc(hidden)]
@@ -60,6 +66,8 @@ impl<'a> Database<'a> {
     /// This is performed lazily and therefore requires calling `next` on the resulting cursor.
     /// if your are using a command like find or aggregate `command_batch` is likely
     /// more convenient for you.
+    /// Commands can't run in a session or with the read concern of the options, an
+    /// `InvalidParamsError` is returned if either is set.
     pub fn command(
         &'a self,
         command: Document,
@@ -71,6 +79,12 @@ impl<'a> Database<'a> {
         let options = options.unwrap_or(&default_options);
         let fields_bsonc = options.fields_bsonc();
 
+        // The legacy command API has no opts to run the command in a session or with a
+        // read concern
+        if options.session.is_some() || options.read_concern.is_some() {
+            return Err(InvalidParamsError.into())
+        }
+
         let cursor_ptr = unsafe {
             bindings::mongoc_database_command(
                 self.inner,
@@ -78,7 +92,7 @@ impl<'a> Database<'a> {
                 options.skip,
                 options.limit,
                 options.batch_size,
-                Bsonc::from_document(&command)?.inner(),
+                Bsonc::from_document(&options.command_with_max_time(command))?.inner(),
                 match fields_bsonc {
                     Some(ref f) => f.inner(),
                     None => ptr::null()
@@ -94,31 +108,41 @@ impl<'a> Database<'a> {
             return Err(InvalidParamsError.into())
         }
 
-        Ok(Cursor::new(
+        let mut cursor = Cursor::new(
             cursor::CreatedBy::Database(self),
             cursor_ptr,
             fields_bsonc
-        ))
+        );
+        cursor.set_utf8_lossy(options.utf8_lossy);
+        Ok(cursor)
     }
 
     /// Execute a command on the database and returns a `BatchCursor`
     /// Automates the process of getting the next batch from getMore
     /// and parses the batch so only the result documents are returned.
     /// I am unsure of the best practices of when to use this or the CRUD function.
+    ///
+    /// The batch size of the options is used for every `getMore`, set `batchSize` in the
+    /// command to configure the first batch.
     pub fn command_batch(
         &'a self,
         command: Document,
         options: Option<&CommandAndFindOptions>
     ) -> Result<BatchCursor<'a>> {
-        let coll_name = get_coll_name_from_doc(&command)?;
+        let coll_name  = get_coll_name_from_doc(&command)?;
+        let read_prefs = options.and_then(|options| options.read_prefs.clone());
+        let batch_size = options.map(|options| options.batch_size).unwrap_or(0);
         Ok(BatchCursor::new(
             self.command(command, options)?,
             self,
-            coll_name
+            coll_name,
+            read_prefs,
+            batch_size
         ))
     }
 
     /// Simplified version of `command` that returns the first document immediately.
+    /// If the command fails the error contains the reply of the server.
     pub fn command_simple(
         &'a self,
         command: Document,
@@ -149,6 +173,66 @@ impl<'a> Database<'a> {
                 Ok(document) => return Ok(document),
                 Err(error)   => return Err(error.into())
             }
+        } else {
+            Err(ReplyError::from_reply(error, &reply))
+        }
+    }
+
+    /// Run a command like `command_simple` and deserialize the reply into a `T`. A reply that
+    /// cannot be deserialized results in a `MongoError::Decoder`.
+    pub fn run_command_typed<T>(
+        &'a self,
+        command: Document,
+        read_prefs: Option<&ReadPrefs>
+    ) -> Result<T> where T: DeserializeOwned {
+        let reply = self.command_simple(command, read_prefs)?;
+        Ok(bson::from_document(reply)?)
+    }
+
+    /// Create a user that authenticates against this database. The roles are an array
+    /// encoded as a document, with the keys `0`, `1` and so on, such as
+    /// `{"0": {"role": "readWrite", "db": "app"}}`. Returns the server error if the user
+    /// already exists.
+    pub fn add_user(&self, username: &str, password: &str, roles: &Document) -> Result<()> {
+        assert!(!self.inner.is_null());
+
+        let username_cstring = CString::new(username)?;
+        let password_cstring = CString::new(password)?;
+        let mut error        = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_database_add_user(
+                self.inner,
+                username_cstring.as_ptr(),
+                password_cstring.as_ptr(),
+                Bsonc::from_document(roles)?.inner(),
+                ptr::null(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Remove a user from this database. Returns the server error if there is no such user.
+    pub fn remove_user(&self, username: &str) -> Result<()> {
+        assert!(!self.inner.is_null());
+
+        let username_cstring = CString::new(username)?;
+        let mut error        = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_database_remove_user(
+                self.inner,
+                username_cstring.as_ptr(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
         } else {
             Err(error.into())
         }
@@ -188,6 +272,23 @@ impl<'a> Database<'a> {
         }
     }
 
+    /// Drop the database, including all its collections and indexes. Dropping a database
+    /// that doesn't exist succeeds.
+    pub fn drop(&mut self) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_database_drop(
+                self.inner,
+                error.mut_inner()
+            )
+        };
+        if success == 0 {
+            return Err(error.into())
+        }
+        Ok(())
+    }
+
     /// Borrow a collection
     pub fn get_collection<S: Into<Vec<u8>>>(&self, collection: S) -> Collection {
         assert!(!self.inner.is_null());
@@ -219,6 +320,96 @@ impl<'a> Database<'a> {
         String::from_utf8_lossy(cstr.to_bytes())
     }
 
+    /// Get the write concern of this database.
+    pub fn get_write_concern(&self) -> WriteConcern {
+        assert!(!self.inner.is_null());
+        WriteConcern::from_copy(unsafe { bindings::mongoc_database_get_write_concern(self.inner) })
+    }
+
+    /// Set the write concern of this database. Collections that are obtained from the database
+    /// afterwards use it for writes that don't set their own write concern.
+    pub fn set_write_concern(&mut self, write_concern: &WriteConcern) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_database_set_write_concern(self.inner, write_concern.inner());
+        }
+    }
+
+    /// Get the read concern of this database.
+    pub fn get_read_concern(&self) -> ReadConcern {
+        assert!(!self.inner.is_null());
+        ReadConcern::from_copy(unsafe { bindings::mongoc_database_get_read_concern(self.inner) })
+    }
+
+    /// Set the read concern of this database. Collections that are obtained from the database
+    /// afterwards use it for reads that don't set their own read concern.
+    pub fn set_read_concern(&mut self, read_concern: &ReadConcern) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_database_set_read_concern(self.inner, read_concern.inner());
+        }
+    }
+
+    /// Get the read prefs of this database.
+    pub fn get_read_prefs(&self) -> ReadPrefs {
+        assert!(!self.inner.is_null());
+        ReadPrefs::from_copy(unsafe { bindings::mongoc_database_get_read_prefs(self.inner) })
+    }
+
+    /// Set the read prefs of this database. Collections that are obtained from the database
+    /// afterwards use them for reads that don't set their own read prefs.
+    pub fn set_read_prefs(&mut self, read_prefs: &ReadPrefs) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_database_set_read_prefs(self.inner, read_prefs.inner());
+        }
+    }
+
+    /// Get the names of the collections in this database, optionally only the ones
+    /// matching a `listCollections` filter.
+    pub fn get_collection_names(&self, filter: Option<&Document>) -> Result<Vec<String>> {
+        assert!(!self.inner.is_null());
+
+        let opts_bsonc = match filter {
+            Some(f) => Some(Bsonc::from_document(&doc! { "filter": f.clone() })?),
+            None => None
+        };
+        let mut error = BsoncError::empty();
+
+        let names = unsafe {
+            bindings::mongoc_database_get_collection_names_with_opts(
+                self.inner,
+                match opts_bsonc {
+                    Some(ref o) => o.inner(),
+                    None => ptr::null()
+                },
+                error.mut_inner()
+            )
+        };
+
+        if names.is_null() {
+            Err(error.into())
+        } else {
+            Ok(unsafe { take_strv(names) })
+        }
+    }
+
+    /// Watch all collections in this database for changes. The pipeline contains aggregation
+    /// stages, such as `$match`, that filter or transform the change events on the server.
+    pub fn watch(
+        &'a self,
+        pipeline: &[Document],
+        options:  Option<&ChangeStreamOptions>
+    ) -> Result<ChangeStream<'a>> {
+        assert!(!self.inner.is_null());
+        ChangeStream::watch(
+            change_stream::CreatedBy::Database(self),
+            pipeline,
+            options,
+            |pipeline, opts| unsafe { bindings::mongoc_database_watch(self.inner, pipeline, opts) }
+        )
+    }
+
     /// This function checks to see if a collection exists on the MongoDB server within database.
     pub fn has_collection<S: Into<Vec<u8>>>(
         &self,
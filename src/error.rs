@@ -1,5 +1,6 @@
 use std::error;
 use std::fmt;
+use std::io;
 use std::borrow::Cow;
 use std::ffi::CStr;
 
@@ -9,6 +10,7 @@ use bson::{de,ser,Document};
 use bson::document::ValueAccessError;
 
 use crate::mongoc::bindings;
+use crate::bsonc::Bsonc;
 
 /// Wrapper for all errors that can occur in the driver.
 pub enum MongoError {
@@ -23,7 +25,12 @@ pub enum MongoError {
     /// Invalid params error that can be reported by the underlying C driver.
     InvalidParams(InvalidParamsError),
     // from CString::new(db)
-    Nul(NulError)
+    Nul(NulError),
+    /// Error reading from or writing to a stream.
+    Io(io::Error),
+    /// Error in the underlying C driver with the reply document the server sent. Boxed
+    /// so the reply doesn't grow every `Result`.
+    Reply(Box<ReplyError>)
 }
 
 impl fmt::Display for MongoError {
@@ -34,7 +41,9 @@ impl fmt::Display for MongoError {
             MongoError::Decoder(ref err) => write!(f, "{}", err),
             MongoError::ValueAccessError(ref err) => write!(f, "{}", err),
             MongoError::InvalidParams(ref err) => write!(f, "{}", err),
-            MongoError::Nul(ref err) => write!(f, "{}", err)
+            MongoError::Nul(ref err) => write!(f, "{}", err),
+            MongoError::Io(ref err) => write!(f, "{}", err),
+            MongoError::Reply(ref err) => write!(f, "{}", err)
         }
     }
 }
@@ -47,7 +56,9 @@ impl fmt::Debug for MongoError {
             MongoError::Encoder(ref err) => write!(f, "MongoError ({:?})", err),
             MongoError::ValueAccessError(ref err) => write!(f, "MongoError ({:?})", err),
             MongoError::InvalidParams(ref err) => write!(f, "MongoError ({:?})", err),
-            MongoError::Nul(ref err) => write!(f, "MongoError ({:?})", err)
+            MongoError::Nul(ref err) => write!(f, "MongoError ({:?})", err),
+            MongoError::Io(ref err) => write!(f, "MongoError ({:?})", err),
+            MongoError::Reply(ref err) => write!(f, "MongoError ({:?})", err)
         }
     }
 }
@@ -60,7 +71,51 @@ impl error::Error for MongoError {
             MongoError::Encoder(ref err) => Some(err),
             MongoError::ValueAccessError(ref err) => Some(err),
             MongoError::InvalidParams(ref err) => Some(err),
-            MongoError::Nul(ref err) => Some(err)
+            MongoError::Nul(ref err) => Some(err),
+            MongoError::Io(ref err) => Some(err),
+            MongoError::Reply(ref err) => Some(err)
+        }
+    }
+}
+
+impl MongoError {
+    /// The error reported by the underlying C driver, for both `Bsonc` errors and `Reply`
+    /// errors that also carry the reply of the server.
+    pub fn bsonc_error(&self) -> Option<&BsoncError> {
+        match *self {
+            MongoError::Bsonc(ref err) => Some(err),
+            MongoError::Reply(ref err) => Some(&err.error),
+            _ => None
+        }
+    }
+
+    /// Whether the error occurred on the connection to the server, see `BsoncError::is_network_error`.
+    pub fn is_network_error(&self) -> bool {
+        match *self {
+            MongoError::Bsonc(ref err) => err.is_network_error(),
+            MongoError::Reply(ref err) => err.error.is_network_error(),
+            _ => false
+        }
+    }
+
+    /// Whether the operation might succeed when it is retried, see `BsoncError::is_retryable`.
+    /// Errors with a `RetryableWriteError` label are retryable as well.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            MongoError::Bsonc(ref err) => err.is_retryable(),
+            MongoError::Reply(ref err) => {
+                err.error.is_retryable() || err.has_error_label("RetryableWriteError")
+            },
+            _ => false
+        }
+    }
+
+    /// Whether the server labeled the error, such as with `TransientTransactionError` when a
+    /// transaction can be retried as a whole.
+    pub fn has_error_label(&self, label: &str) -> bool {
+        match *self {
+            MongoError::Reply(ref err) => err.has_error_label(label),
+            _ => false
         }
     }
 }
@@ -89,6 +144,12 @@ impl From<NulError> for MongoError {
     }
 }
 
+impl From<io::Error> for MongoError {
+    fn from(error: io::Error) -> MongoError {
+        MongoError::Io(error)
+    }
+}
+
 /// Error in the underlying C driver.
 pub struct BsoncError {
     inner: bindings::bson_error_t,
@@ -112,9 +173,29 @@ pub enum MongoErrorDomain {
     Collection,
     Gridfs,
     Scram,
+    ServerSelection,
+    WriteConcern,
+    Server,
+    Transaction,
     Unknown
 }
 
+/// Server error codes of transient errors, a write that failed with one of these can be retried.
+const RETRYABLE_SERVER_CODES: &[u32] = &[
+    6,     // HostUnreachable
+    7,     // HostNotFound
+    89,    // NetworkTimeout
+    91,    // ShutdownInProgress
+    189,   // PrimarySteppedDown
+    262,   // ExceededTimeLimit
+    9001,  // SocketException
+    10107, // NotWritablePrimary
+    11600, // InterruptedAtShutdown
+    11602, // InterruptedDueToReplStateChange
+    13435, // NotPrimaryNoSecondaryOk
+    13436  // NotPrimaryOrSecondary
+];
+
 /// MongoDB error code.
 #[derive(Debug,PartialEq)]
 pub enum MongoErrorCode {
@@ -152,6 +233,22 @@ pub enum MongoErrorCode {
     QueryNotTailable,
     WriteConcernError,
     DuplicateKey,
+    CursorNotFound,
+    MaxTimeMsExpired,
+    NetworkTimeout,
+    ShutdownInProgress,
+    WriteConflict,
+    DocumentValidationFailure,
+    PrimarySteppedDown,
+    NoSuchTransaction,
+    ExceededTimeLimit,
+    SocketException,
+    NotWritablePrimary,
+    InterruptedAtShutdown,
+    InterruptedDueToReplStateChange,
+    StaleConfig,
+    NotPrimaryNoSecondaryOk,
+    NotPrimaryOrSecondary,
     Unknown(u32)
 }
 
@@ -174,22 +271,26 @@ impl BsoncError {
     /// The error's domain.
     pub fn domain(&self) -> MongoErrorDomain {
         match self.inner.domain {
-            0                                 => MongoErrorDomain::Blank,
-            bindings::MONGOC_ERROR_CLIENT     => MongoErrorDomain::Client,
-            bindings::MONGOC_ERROR_STREAM     => MongoErrorDomain::Stream,
-            bindings::MONGOC_ERROR_PROTOCOL   => MongoErrorDomain::Protocol,
-            bindings::MONGOC_ERROR_CURSOR     => MongoErrorDomain::Cursor,
-            bindings::MONGOC_ERROR_QUERY      => MongoErrorDomain::Query,
-            bindings::MONGOC_ERROR_INSERT     => MongoErrorDomain::Insert,
-            bindings::MONGOC_ERROR_SASL       => MongoErrorDomain::Sasl,
-            bindings::MONGOC_ERROR_BSON       => MongoErrorDomain::Bson,
-            bindings::MONGOC_ERROR_MATCHER    => MongoErrorDomain::Matcher,
-            bindings::MONGOC_ERROR_NAMESPACE  => MongoErrorDomain::Namespace,
-            bindings::MONGOC_ERROR_COMMAND    => MongoErrorDomain::Command,
-            bindings::MONGOC_ERROR_COLLECTION => MongoErrorDomain::Collection,
-            bindings::MONGOC_ERROR_GRIDFS     => MongoErrorDomain::Gridfs,
-            bindings::MONGOC_ERROR_SCRAM      => MongoErrorDomain::Scram,
-            _                                 => MongoErrorDomain::Unknown
+            0                                       => MongoErrorDomain::Blank,
+            bindings::MONGOC_ERROR_CLIENT           => MongoErrorDomain::Client,
+            bindings::MONGOC_ERROR_STREAM           => MongoErrorDomain::Stream,
+            bindings::MONGOC_ERROR_PROTOCOL         => MongoErrorDomain::Protocol,
+            bindings::MONGOC_ERROR_CURSOR           => MongoErrorDomain::Cursor,
+            bindings::MONGOC_ERROR_QUERY            => MongoErrorDomain::Query,
+            bindings::MONGOC_ERROR_INSERT           => MongoErrorDomain::Insert,
+            bindings::MONGOC_ERROR_SASL             => MongoErrorDomain::Sasl,
+            bindings::MONGOC_ERROR_BSON             => MongoErrorDomain::Bson,
+            bindings::MONGOC_ERROR_MATCHER          => MongoErrorDomain::Matcher,
+            bindings::MONGOC_ERROR_NAMESPACE        => MongoErrorDomain::Namespace,
+            bindings::MONGOC_ERROR_COMMAND          => MongoErrorDomain::Command,
+            bindings::MONGOC_ERROR_COLLECTION       => MongoErrorDomain::Collection,
+            bindings::MONGOC_ERROR_GRIDFS           => MongoErrorDomain::Gridfs,
+            bindings::MONGOC_ERROR_SCRAM            => MongoErrorDomain::Scram,
+            bindings::MONGOC_ERROR_SERVER_SELECTION => MongoErrorDomain::ServerSelection,
+            bindings::MONGOC_ERROR_WRITE_CONCERN    => MongoErrorDomain::WriteConcern,
+            bindings::MONGOC_ERROR_SERVER           => MongoErrorDomain::Server,
+            bindings::MONGOC_ERROR_TRANSACTION      => MongoErrorDomain::Transaction,
+            _                                       => MongoErrorDomain::Unknown
         }
     }
 
@@ -230,10 +331,57 @@ impl BsoncError {
             bindings::MONGOC_ERROR_QUERY_NOT_TAILABLE            => MongoErrorCode::QueryNotTailable,
             bindings::MONGOC_ERROR_WRITE_CONCERN_ERROR           => MongoErrorCode::WriteConcernError,
             bindings::MONGOC_ERROR_DUPLICATE_KEY                 => MongoErrorCode::DuplicateKey,
+            43                                                   => MongoErrorCode::CursorNotFound,
+            50                                                   => MongoErrorCode::MaxTimeMsExpired,
+            89                                                   => MongoErrorCode::NetworkTimeout,
+            91                                                   => MongoErrorCode::ShutdownInProgress,
+            112                                                  => MongoErrorCode::WriteConflict,
+            121                                                  => MongoErrorCode::DocumentValidationFailure,
+            189                                                  => MongoErrorCode::PrimarySteppedDown,
+            251                                                  => MongoErrorCode::NoSuchTransaction,
+            262                                                  => MongoErrorCode::ExceededTimeLimit,
+            9001                                                 => MongoErrorCode::SocketException,
+            10107                                                => MongoErrorCode::NotWritablePrimary,
+            11600                                                => MongoErrorCode::InterruptedAtShutdown,
+            11602                                                => MongoErrorCode::InterruptedDueToReplStateChange,
+            13388                                                => MongoErrorCode::StaleConfig,
+            13435                                                => MongoErrorCode::NotPrimaryNoSecondaryOk,
+            13436                                                => MongoErrorCode::NotPrimaryOrSecondary,
             code                                                 => MongoErrorCode::Unknown(code)
         }
     }
 
+    /// Whether the error occurred on the connection to the server, such as a socket error or a
+    /// timeout, instead of being reported by the server.
+    pub fn is_network_error(&self) -> bool {
+        self.domain() == MongoErrorDomain::Stream
+    }
+
+    /// Whether the error is transient, so the operation might succeed when it is retried. This
+    /// is the case for socket errors and timeouts and for server errors that occur during a
+    /// failover. Other stream errors, such as an invalid stream state or a failed name
+    /// resolution, are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self.domain() {
+            MongoErrorDomain::Stream => match self.code() {
+                MongoErrorCode::StreamSocket |
+                MongoErrorCode::StreamConnect |
+                MongoErrorCode::StreamNotEstablished => true,
+                _ => false
+            },
+            MongoErrorDomain::Query |
+            MongoErrorDomain::Server |
+            MongoErrorDomain::WriteConcern => RETRYABLE_SERVER_CODES.contains(&self.inner.code),
+            _ => false
+        }
+    }
+
+    /// The raw error code. For errors reported by the server this is the server error code, such
+    /// as 11000 for a duplicate key, which is useful for codes `code` does not cover.
+    pub fn server_code(&self) -> i32 {
+        self.inner.code as i32
+    }
+
     /// The error's message.
     pub fn get_message(&self) -> Cow<str> {
         let cstr = unsafe { CStr::from_ptr(&self.inner.message as *const i8) };
@@ -303,13 +451,108 @@ impl From<InvalidParamsError> for MongoError {
     }
 }
 
+/// Error in the underlying C driver that includes the reply document of the server.
+#[derive(Debug)]
+pub struct ReplyError {
+    /// Returned error
+    pub error: BsoncError,
+    /// Reply of the server
+    pub reply: Document
+}
+
+impl ReplyError {
+    /// Create an error that keeps the reply, if there is one. Returns a plain `Bsonc` error
+    /// when the reply is empty, such as for network errors.
+    pub(crate) fn from_reply(error: BsoncError, reply: &Bsonc) -> MongoError {
+        match reply.as_document() {
+            Ok(ref reply) if reply.is_empty() => error.into(),
+            Ok(reply) => MongoError::Reply(Box::new(ReplyError {
+                error: error,
+                reply: reply
+            })),
+            Err(_) => error.into()
+        }
+    }
+
+    /// Labels the server added to the error in the `errorLabels` array of the reply.
+    pub fn error_labels(&self) -> Vec<&str> {
+        match self.reply.get_array("errorLabels") {
+            Ok(labels) => labels.iter().filter_map(|label| label.as_str()).collect(),
+            Err(_) => Vec::new()
+        }
+    }
+
+    /// Whether the server added the label to the error.
+    pub fn has_error_label(&self, label: &str) -> bool {
+        self.error_labels().contains(&label)
+    }
+}
+
+impl fmt::Display for ReplyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl error::Error for ReplyError {
+    fn description(&self) -> &str {
+        "Error reported by the underlying Mongo C driver that includes the reply document"
+    }
+}
+
 /// Error returned by a bulk operation that includes a report in the reply document.
 #[derive(Debug)]
 pub struct BulkOperationError {
     /// Returned error
     pub error: MongoError,
     /// Error report
-    pub reply: Document
+    pub reply: Document,
+    /// Write errors from the `writeErrors` array in the reply
+    pub write_errors: Vec<BulkWriteError>,
+    /// Write concern errors from the `writeConcernErrors` array in the reply
+    pub write_concern_errors: Vec<WriteConcernError>
+}
+
+impl BulkOperationError {
+    pub(crate) fn new(error: MongoError, reply: Document) -> BulkOperationError {
+        let write_errors = parse_reply_errors(&reply, "writeErrors");
+        let write_concern_errors = parse_reply_errors(&reply, "writeConcernErrors");
+        BulkOperationError {
+            error:                error,
+            reply:                reply,
+            write_errors:         write_errors,
+            write_concern_errors: write_concern_errors
+        }
+    }
+}
+
+fn parse_reply_errors<T>(reply: &Document, key: &str) -> Vec<T> where T: serde::de::DeserializeOwned {
+    match reply.get_array(key) {
+        Ok(errors) => errors.iter()
+            .filter_map(|error| bson::from_bson(error.clone()).ok())
+            .collect(),
+        Err(_) => Vec::new()
+    }
+}
+
+/// Error for a single operation in a bulk operation.
+#[derive(Debug,Deserialize,PartialEq)]
+pub struct BulkWriteError {
+    /// Index of the operation that failed, in the order operations were added
+    pub index:  u32,
+    /// Server error code
+    pub code:   i32,
+    /// Error message
+    pub errmsg: String
+}
+
+/// Error reported when a write concern could not be satisfied.
+#[derive(Debug,Deserialize,PartialEq)]
+pub struct WriteConcernError {
+    /// Server error code
+    pub code:   i32,
+    /// Error message
+    pub errmsg: String
 }
 
 impl fmt::Display for BulkOperationError {
@@ -326,7 +569,10 @@ impl error::Error for BulkOperationError {
 
 #[cfg(test)]
 mod tests {
-    use super::{BsoncError,MongoErrorDomain,MongoErrorCode};
+    use bson::doc;
+
+    use super::{BsoncError,MongoError,MongoErrorDomain,MongoErrorCode,InvalidParamsError,ReplyError};
+    use crate::mongoc::bindings;
 
     #[test]
     fn test_bson_error_empty() {
@@ -353,5 +599,89 @@ mod tests {
         assert_eq!(MongoErrorCode::Blank, error.code());
         error.mut_inner().code = 1;
         assert_eq!(MongoErrorCode::StreamInvalidType, error.code());
+        error.mut_inner().code = 112;
+        assert_eq!(MongoErrorCode::WriteConflict, error.code());
+        assert_eq!(112, error.server_code());
+        error.mut_inner().code = 99999;
+        assert_eq!(MongoErrorCode::Unknown(99999), error.code());
+    }
+
+    #[test]
+    fn test_bson_error_network_error_is_retryable() {
+        let mut error = BsoncError::empty();
+        error.mut_inner().domain = bindings::MONGOC_ERROR_STREAM;
+        error.mut_inner().code = bindings::MONGOC_ERROR_STREAM_SOCKET;
+        assert!(error.is_network_error());
+        assert!(error.is_retryable());
+
+        let error: MongoError = error.into();
+        assert!(error.is_network_error());
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_bson_error_stream_state_error_is_not_retryable() {
+        let mut error = BsoncError::empty();
+        error.mut_inner().domain = bindings::MONGOC_ERROR_STREAM;
+        error.mut_inner().code = bindings::MONGOC_ERROR_STREAM_INVALID_STATE;
+        assert!(error.is_network_error());
+        assert!(!error.is_retryable());
+
+        error.mut_inner().code = bindings::MONGOC_ERROR_STREAM_NAME_RESOLUTION;
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_bson_error_query_failure_is_not_retryable() {
+        let mut error = BsoncError::empty();
+        error.mut_inner().domain = bindings::MONGOC_ERROR_QUERY;
+        error.mut_inner().code = bindings::MONGOC_ERROR_QUERY_FAILURE;
+        assert!(!error.is_network_error());
+        assert!(!error.is_retryable());
+
+        let error: MongoError = InvalidParamsError.into();
+        assert!(!error.is_network_error());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_bson_error_transient_server_error_is_retryable() {
+        let mut error = BsoncError::empty();
+        error.mut_inner().domain = bindings::MONGOC_ERROR_SERVER;
+        error.mut_inner().code = 10107;
+        assert!(!error.is_network_error());
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_reply_error_labels() {
+        let error = MongoError::Reply(Box::new(ReplyError {
+            error: BsoncError::empty(),
+            reply: doc! { "errorLabels": ["TransientTransactionError", "RetryableWriteError"] }
+        }));
+        assert!(error.has_error_label("TransientTransactionError"));
+        assert!(!error.has_error_label("UnknownTransactionCommitResult"));
+        assert!(error.is_retryable());
+
+        let error: MongoError = BsoncError::empty().into();
+        assert!(!error.has_error_label("TransientTransactionError"));
+    }
+
+    #[test]
+    fn test_bsonc_error_for_reply_error() {
+        let mut inner = BsoncError::empty();
+        inner.mut_inner().domain = bindings::MONGOC_ERROR_SERVER;
+        inner.mut_inner().code = 11000;
+        let error = MongoError::Reply(Box::new(ReplyError {
+            error: inner,
+            reply: doc! { "ok": 0 }
+        }));
+        assert_eq!(MongoErrorCode::DuplicateKey, error.bsonc_error().unwrap().code());
+
+        let error: MongoError = BsoncError::empty().into();
+        assert!(error.bsonc_error().is_some());
+
+        let error: MongoError = InvalidParamsError.into();
+        assert!(error.bsonc_error().is_none());
     }
 }
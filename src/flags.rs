@@ -21,6 +21,11 @@ impl <T> Flags<T> where T: Ord {
     pub fn add(&mut self, flag: T) {
         self.flags.insert(flag);
     }
+
+    /// Whether this instance contains a flag
+    pub fn contains(&self, flag: &T) -> bool {
+        self.flags.contains(flag)
+    }
 }
 
 /// To provide the combined value of all flags.
@@ -0,0 +1,459 @@
+//! Store, retrieve and remove large files with GridFS.
+//!
+//! GridFS splits a file into chunks that are stored in the `<prefix>.chunks` collection, the
+//! metadata of the file is stored in the `<prefix>.files` collection.
+
+use std::ffi::{CStr,CString};
+use std::io;
+use std::io::{Read,Seek,SeekFrom,Write};
+use std::mem;
+use std::ptr;
+
+use crate::mongoc::bindings;
+
+use bson::{doc,Bson,Document};
+use bson::document::ValueAccessError;
+use bson::oid::ObjectId;
+
+use super::Result;
+use super::{BsoncError,MongoError,MongoErrorDomain};
+use super::bsonc::Bsonc;
+use super::client::Client;
+use super::collection::Collection;
+use super::cursor;
+use super::cursor::Cursor;
+
+/// Size of the buffer used to copy data from and to streams, equal to the default chunk size.
+const BUFFER_SIZE: usize = 255 * 1024;
+
+/// Provides access to a GridFS bucket to upload, download, find and delete files.
+///
+/// A `GridFs` is obtained by calling `get_gridfs` on a `Client`.
+pub struct GridFs<'a> {
+    client:   &'a Client<'a>,
+    database: *mut bindings::mongoc_database_t,
+    prefix:   String,
+    inner:    *mut bindings::mongoc_gridfs_bucket_t
+}
+
+impl<'a> GridFs<'a> {
+    pub(crate) fn new(
+        client:   &'a Client<'a>,
+        database: *mut bindings::mongoc_database_t,
+        prefix:   &str
+    ) -> Result<GridFs<'a>> {
+        assert!(!database.is_null());
+
+        let opts_bsonc = Bsonc::from_document(&doc! { "bucketName": prefix })?;
+        let mut error  = BsoncError::empty();
+        let inner = unsafe {
+            bindings::mongoc_gridfs_bucket_new(
+                database,
+                opts_bsonc.inner(),
+                ptr::null(),
+                error.mut_inner()
+            )
+        };
+
+        if inner.is_null() {
+            unsafe { bindings::mongoc_database_destroy(database) };
+            Err(error.into())
+        } else {
+            Ok(GridFs {
+                client:   client,
+                database: database,
+                prefix:   prefix.to_string(),
+                inner:    inner
+            })
+        }
+    }
+
+    /// Upload a file by reading the reader until the end. Returns the id of the new file.
+    ///
+    /// If reading or writing fails the chunks that were already uploaded are removed.
+    pub fn upload_from_stream(&self, filename: &str, reader: &mut dyn Read) -> Result<ObjectId> {
+        assert!(!self.inner.is_null());
+
+        let id               = ObjectId::new();
+        let id_value         = oid_value(&id);
+        let filename_cstring = CString::new(filename)?;
+        let mut error        = BsoncError::empty();
+
+        let stream = unsafe {
+            bindings::mongoc_gridfs_bucket_open_upload_stream_with_id(
+                self.inner,
+                &id_value,
+                filename_cstring.as_ptr(),
+                ptr::null(),
+                error.mut_inner()
+            )
+        };
+        if stream.is_null() {
+            return Err(error.into())
+        }
+
+        let result = copy_to_stream(reader, stream).and_then(|_| {
+            if unsafe { bindings::mongoc_stream_close(stream) } == 0 {
+                Ok(())
+            } else {
+                Err(stream_error(stream))
+            }
+        });
+
+        unsafe {
+            if result.is_err() {
+                bindings::mongoc_gridfs_bucket_abort_upload(stream);
+            }
+            bindings::mongoc_stream_destroy(stream);
+        }
+
+        result.map(|_| id)
+    }
+
+    /// Download the file with the given id and write it to the writer.
+    pub fn download_to_stream(&self, id: &ObjectId, writer: &mut dyn Write) -> Result<()> {
+        let stream = self.open_stream(id)?;
+        let result = copy_from_stream(stream, writer);
+
+        unsafe {
+            bindings::mongoc_stream_destroy(stream);
+        }
+
+        result
+    }
+
+    /// Open the file with the given id for reading. The returned stream implements `Read`
+    /// and `Seek`, so parts of a large file can be read without downloading all of it.
+    pub fn open_download_stream(&'a self, id: &ObjectId) -> Result<GridFsDownloadStream<'a>> {
+        let stream = self.open_stream(id)?;
+        // Closes the stream if reading the file document fails
+        let mut download_stream = GridFsDownloadStream {
+            chunks:          self.chunks_collection(),
+            id:              id.clone(),
+            length:          0,
+            chunk_size:      0,
+            position:        0,
+            stream:          stream,
+            stream_position: 0,
+            chunk:           None
+        };
+
+        let file = match self.find(&doc! { "_id": id.clone() }, None)?.next() {
+            Some(file) => file?,
+            None => return Err(MongoError::ValueAccessError(ValueAccessError::NotPresent))
+        };
+        download_stream.length     = integer_field(&file, "length")?;
+        download_stream.chunk_size = integer_field(&file, "chunkSize")?;
+        if download_stream.chunk_size == 0 && download_stream.length > 0 {
+            return Err(MongoError::ValueAccessError(ValueAccessError::UnexpectedType))
+        }
+        Ok(download_stream)
+    }
+
+    /// Find files in the bucket. The cursor returns the documents in the files collection,
+    /// containing the `_id`, `filename`, `length`, `uploadDate` and `metadata` of a file.
+    ///
+    /// Options such as `sort` or `limit` are passed on to the find on the files collection.
+    pub fn find(&'a self, filter: &Document, opts: Option<&Document>) -> Result<Cursor<'a>> {
+        assert!(!self.inner.is_null());
+
+        let opts_bsonc = match opts {
+            Some(o) => Some(Bsonc::from_document(o)?),
+            None => None
+        };
+        let cursor_ptr = unsafe {
+            bindings::mongoc_gridfs_bucket_find(
+                self.inner,
+                Bsonc::from_document(filter)?.inner(),
+                match opts_bsonc {
+                    Some(ref o) => o.inner(),
+                    None => ptr::null()
+                }
+            )
+        };
+
+        Ok(Cursor::new(
+            cursor::CreatedBy::GridFs(self),
+            cursor_ptr,
+            None
+        ))
+    }
+
+    /// Delete the file with the given id and all its chunks. Returns a `NotPresent` value access
+    /// error if there is no file with this id.
+    pub fn delete(&self, id: &Bson) -> Result<()> {
+        assert!(!self.inner.is_null());
+
+        let id_bsonc  = Bsonc::from_document(&doc! { "id": id.clone() })?;
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            let mut iter: bindings::bson_iter_t = mem::zeroed();
+            let found = bindings::bson_iter_init_find(
+                &mut iter,
+                id_bsonc.inner(),
+                b"id\0".as_ptr() as *const ::libc::c_char
+            );
+            assert!(found == 1);
+            bindings::mongoc_gridfs_bucket_delete_by_id(
+                self.inner,
+                bindings::bson_iter_value(&mut iter),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else if error.domain() == MongoErrorDomain::Gridfs &&
+            error.server_code() == bindings::MONGOC_ERROR_GRIDFS_BUCKET_FILE_NOT_FOUND as i32 {
+            Err(MongoError::ValueAccessError(ValueAccessError::NotPresent))
+        } else {
+            Err(error.into())
+        }
+    }
+}
+
+impl<'a> GridFs<'a> {
+    fn open_stream(&self, id: &ObjectId) -> Result<*mut bindings::mongoc_stream_t> {
+        assert!(!self.inner.is_null());
+
+        let id_value  = oid_value(id);
+        let mut error = BsoncError::empty();
+
+        let stream = unsafe {
+            bindings::mongoc_gridfs_bucket_open_download_stream(
+                self.inner,
+                &id_value,
+                error.mut_inner()
+            )
+        };
+        if stream.is_null() {
+            Err(error.into())
+        } else {
+            Ok(stream)
+        }
+    }
+
+    fn chunks_collection(&self) -> Collection<'a> {
+        let db_name = unsafe {
+            CStr::from_ptr(bindings::mongoc_database_get_name(self.database)).to_bytes().to_vec()
+        };
+        self.client.get_collection(db_name, format!("{}.chunks", self.prefix))
+    }
+}
+
+impl<'a> Drop for GridFs<'a> {
+    fn drop(&mut self) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_gridfs_bucket_destroy(self.inner);
+            bindings::mongoc_database_destroy(self.database);
+        }
+    }
+}
+
+/// Stream to read a GridFS file, obtained by calling `open_download_stream` on a `GridFs`.
+///
+/// Reads from the start of the file are streamed by mongoc. After seeking to another position
+/// the stream reads the chunk that contains the position, so only the chunks that are needed
+/// are fetched and at most one chunk is held in memory. Seeking past the end of the file is
+/// allowed, reads there return no data.
+pub struct GridFsDownloadStream<'a> {
+    chunks:          Collection<'a>,
+    id:              ObjectId,
+    length:          u64,
+    chunk_size:      u64,
+    position:        u64,
+    // The mongoc stream is only used while reads follow on from its position
+    stream:          *mut bindings::mongoc_stream_t,
+    stream_position: u64,
+    // Number and data of the last chunk that was fetched
+    chunk:           Option<(u64, Vec<u8>)>
+}
+
+impl<'a> GridFsDownloadStream<'a> {
+    /// Length of the file in bytes.
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    /// Whether the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    fn close_stream(&mut self) {
+        if !self.stream.is_null() {
+            unsafe {
+                bindings::mongoc_stream_destroy(self.stream);
+            }
+            self.stream = ptr::null_mut();
+        }
+    }
+
+    fn read_from_stream(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = unsafe {
+            bindings::mongoc_stream_read(
+                self.stream,
+                buf.as_mut_ptr() as *mut ::libc::c_void,
+                buf.len() as bindings::size_t,
+                1,
+                0
+            )
+        };
+        if read < 0 {
+            return Err(io::Error::new(io::ErrorKind::Other, stream_error(self.stream).to_string()))
+        }
+        self.stream_position += read as u64;
+        Ok(read as usize)
+    }
+
+    fn read_from_chunk(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n      = self.position / self.chunk_size;
+        let offset = (self.position % self.chunk_size) as usize;
+
+        let is_cached = match self.chunk {
+            Some((cached_n, _)) => cached_n == n,
+            None => false
+        };
+        if !is_cached {
+            let query = doc! { "files_id": self.id.clone(), "n": n as i64 };
+            let chunk = match self.chunks.find(&query, None).and_then(|mut cursor| cursor.next().transpose()) {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Missing chunk {}", n))),
+                Err(error) => return Err(io::Error::new(io::ErrorKind::Other, error.to_string()))
+            };
+            let data = match chunk.get("data") {
+                Some(&Bson::Binary(ref binary)) => binary.bytes.clone(),
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Invalid chunk {}", n)))
+            };
+            self.chunk = Some((n, data));
+        }
+
+        let data = &self.chunk.as_ref().expect("The chunk should have been fetched").1;
+        if offset >= data.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Chunk {} is too short", n)))
+        }
+        let read = buf.len().min(data.len() - offset);
+        buf[..read].copy_from_slice(&data[offset..offset + read]);
+        Ok(read)
+    }
+}
+
+impl<'a> Read for GridFsDownloadStream<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() || self.position >= self.length {
+            return Ok(0)
+        }
+        let remaining = (self.length - self.position).min(buf.len() as u64) as usize;
+        let buf       = &mut buf[..remaining];
+
+        if !self.stream.is_null() && self.stream_position != self.position {
+            self.close_stream();
+        }
+        let read = if self.stream.is_null() {
+            self.read_from_chunk(buf)?
+        } else {
+            self.read_from_stream(buf)?
+        };
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<'a> Seek for GridFsDownloadStream<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset)   => Some(offset),
+            SeekFrom::End(offset)     => self.length.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset)
+        };
+        match position {
+            Some(position) => {
+                self.position = position;
+                Ok(position)
+            },
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid seek to a negative or overflowing position"))
+        }
+    }
+}
+
+impl<'a> Drop for GridFsDownloadStream<'a> {
+    fn drop(&mut self) {
+        self.close_stream();
+    }
+}
+
+/// Read a non-negative integer field of a files collection document.
+fn integer_field(file: &Document, key: &str) -> Result<u64> {
+    match file.get(key) {
+        Some(&Bson::Int32(value)) if value >= 0 => Ok(value as u64),
+        Some(&Bson::Int64(value)) if value >= 0 => Ok(value as u64),
+        _ => Err(MongoError::ValueAccessError(ValueAccessError::UnexpectedType))
+    }
+}
+
+/// Build the bson value mongoc uses to identify a file.
+fn oid_value(id: &ObjectId) -> bindings::bson_value_t {
+    let mut value = bindings::bson_value_t {
+        value_type: bindings::BSON_TYPE_OID,
+        padding:    0,
+        value:      [0; 3]
+    };
+    let bytes = id.bytes();
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), value.value.as_mut_ptr() as *mut u8, bytes.len());
+    }
+    value
+}
+
+/// Get the error of a failed read or write on a GridFS stream.
+fn stream_error(stream: *mut bindings::mongoc_stream_t) -> MongoError {
+    let mut error = BsoncError::empty();
+    unsafe {
+        bindings::mongoc_gridfs_bucket_stream_error(stream, error.mut_inner());
+    }
+    error.into()
+}
+
+fn copy_to_stream(reader: &mut dyn Read, stream: *mut bindings::mongoc_stream_t) -> Result<()> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(())
+        }
+        let written = unsafe {
+            bindings::mongoc_stream_write(
+                stream,
+                buffer.as_mut_ptr() as *mut ::libc::c_void,
+                read as bindings::size_t,
+                0
+            )
+        };
+        if written < 0 {
+            return Err(stream_error(stream))
+        }
+    }
+}
+
+fn copy_from_stream(stream: *mut bindings::mongoc_stream_t, writer: &mut dyn Write) -> Result<()> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    loop {
+        let read = unsafe {
+            bindings::mongoc_stream_read(
+                stream,
+                buffer.as_mut_ptr() as *mut ::libc::c_void,
+                BUFFER_SIZE as bindings::size_t,
+                1,
+                0
+            )
+        };
+        if read < 0 {
+            return Err(stream_error(stream))
+        }
+        if read == 0 {
+            return Ok(())
+        }
+        writer.write_all(&buffer[..read as usize])?;
+    }
+}
@@ -0,0 +1,71 @@
+//! Index management for collections.
+
+use bson::{Bson,Document};
+
+/// Describes an index to create on a collection.
+///
+/// ```
+/// # #[macro_use] extern crate bson;
+/// # extern crate mongo_driver;
+/// use mongo_driver::index::IndexModel;
+///
+/// # fn main() {
+/// let model = IndexModel {
+///     keys:    doc! { "email": 1 },
+///     options: Some(doc! { "unique": true })
+/// };
+/// assert_eq!("email_1", model.name());
+/// # }
+/// ```
+pub struct IndexModel {
+    /// Fields to index and their direction or type, for example `{"email": 1}`
+    pub keys:    Document,
+    /// Optional index options such as `name`, `unique` or `partialFilterExpression`
+    pub options: Option<Document>
+}
+
+impl IndexModel {
+    /// Create a model for an index on the given keys without any options.
+    pub fn new(keys: Document) -> IndexModel {
+        IndexModel {
+            keys:    keys,
+            options: None
+        }
+    }
+
+    /// Name of the index. This is the `name` option if it is set, otherwise the name
+    /// the server would generate from the keys, such as `email_1`.
+    pub fn name(&self) -> String {
+        if let Some(Ok(name)) = self.options.as_ref().map(|o| o.get_str("name")) {
+            return name.to_string()
+        }
+        self.keys.iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    &Bson::String(ref s) => s.clone(),
+                    &Bson::Int32(i)      => i.to_string(),
+                    &Bson::Int64(i)      => i.to_string(),
+                    &Bson::Double(d)     => d.to_string(),
+                    other                => other.to_string()
+                };
+                format!("{}_{}", key, value)
+            })
+            .collect::<Vec<String>>()
+            .join("_")
+    }
+
+    pub(crate) fn as_document(&self) -> Document {
+        let mut document = doc! {
+            "key":  self.keys.clone(),
+            "name": self.name()
+        };
+        if let Some(ref options) = self.options {
+            for (key, value) in options.iter() {
+                if key != "name" {
+                    document.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        document
+    }
+}
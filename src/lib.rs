@@ -35,25 +35,39 @@ extern crate log;
 extern crate serde_derive;
 extern crate serde;
 
+#[cfg(feature = "async")]
+extern crate tokio;
+
 use std::ffi::CStr;
 use std::ptr;
 use std::result;
 use std::sync::Once;
+use std::time::Duration;
 
 use crate::mongoc::bindings;
 
+pub mod apm;
+#[cfg(feature = "async")]
+pub mod async_collection;
+pub mod change_stream;
 pub mod client;
 pub mod collection;
 pub mod cursor;
 pub mod database;
 pub mod flags;
+pub mod gridfs;
+pub mod index;
+pub mod projection;
+pub mod read_concern;
 pub mod read_prefs;
+pub mod session;
+pub mod sort;
 pub mod write_concern;
 
 mod bsonc;
 mod error;
 
-pub use crate::error::{MongoError,BsoncError,MongoErrorDomain,MongoErrorCode,InvalidParamsError,BulkOperationError};
+pub use crate::error::{MongoError,BsoncError,MongoErrorDomain,MongoErrorCode,InvalidParamsError,ReplyError,BulkOperationError,BulkWriteError,WriteConcernError};
 
 /// Result that's used in all functions that perform operations on the database.
 pub type Result<T> = result::Result<T, MongoError>;
@@ -102,8 +116,20 @@ unsafe extern "C" fn mongoc_log_handler(
     }
 }
 
+/// Collect a NULL terminated string vector returned by mongoc and free it.
+pub(crate) unsafe fn take_strv(strv: *mut *mut ::libc::c_char) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut i = 0;
+    while !(*strv.add(i)).is_null() {
+        strings.push(CStr::from_ptr(*strv.add(i)).to_string_lossy().into_owned());
+        i += 1;
+    }
+    bindings::bson_strfreev(strv);
+    strings
+}
+
 /// Options to configure both command and find operations.
-pub struct CommandAndFindOptions {
+pub struct CommandAndFindOptions<'s> {
     /// Flags to use
     pub query_flags: flags::Flags<flags::QueryFlag>,
     /// Number of documents to skip, zero to ignore
@@ -115,31 +141,77 @@ pub struct CommandAndFindOptions {
     /// Fields to return, not all commands support this option
     pub fields:      Option<bson::Document>,
     /// Read prefs to use
-    pub read_prefs:  Option<read_prefs::ReadPrefs>
+    pub read_prefs:  Option<read_prefs::ReadPrefs>,
+    /// Read concern to use for a find. Only supported by find, `command` returns an
+    /// `InvalidParamsError` when it's set
+    pub read_concern: Option<read_concern::ReadConcern>,
+    /// Session to run a find in. Only supported by find, `command` returns an
+    /// `InvalidParamsError` when it's set
+    pub session:     Option<&'s session::ClientSession<'s>>,
+    /// Collation to use when comparing strings in a find, such as `{"locale": "fr", "strength": 1}`
+    pub collation:   Option<bson::Document>,
+    /// Index to use for a find, either the name of the index or its key document
+    pub hint:        Option<bson::Bson>,
+    /// Order in which a find returns documents, such as `{"name": 1}`
+    pub sort:        Option<bson::Document>,
+    /// Time limit for processing the operation on the server, an operation that exceeds it
+    /// fails with a `MaxTimeMsExpired` error
+    pub max_time:    Option<Duration>,
+    /// Comment to attach to a find, which shows up in the profiler, `currentOp` and the server logs
+    pub comment:     Option<String>,
+    /// Replace invalid UTF-8 in strings in the results with the replacement character, if this
+    /// is false a document with invalid UTF-8 results in a `MongoError::Decoder` instead
+    pub utf8_lossy:  bool
 }
 
-impl CommandAndFindOptions {
+impl<'s> CommandAndFindOptions<'s> {
     /// Default options used if none are provided.
-    pub fn default() -> CommandAndFindOptions {
+    pub fn default() -> CommandAndFindOptions<'s> {
         CommandAndFindOptions {
             query_flags: flags::Flags::new(),
             skip:        0,
             limit:       0,
             batch_size:  0,
             fields:      None,
-            read_prefs:  None
+            read_prefs:  None,
+            read_concern: None,
+            session:     None,
+            collation:   None,
+            hint:        None,
+            sort:        None,
+            max_time:    None,
+            comment:     None,
+            utf8_lossy:  true
         }
     }
 
-    pub fn with_fields(fields: bson::Document) -> CommandAndFindOptions {
+    pub fn with_fields(fields: bson::Document) -> CommandAndFindOptions<'s> {
         CommandAndFindOptions {
             query_flags: flags::Flags::new(),
             skip:        0,
             limit:       0,
             batch_size:  0,
             fields:      Some(fields),
-            read_prefs:  None
+            read_prefs:  None,
+            read_concern: None,
+            session:     None,
+            collation:   None,
+            hint:        None,
+            sort:        None,
+            max_time:    None,
+            comment:     None,
+            utf8_lossy:  true
+        }
+    }
+
+    /// Add the time limit to a command that doesn't set one itself.
+    fn command_with_max_time(&self, mut command: bson::Document) -> bson::Document {
+        if let Some(max_time) = self.max_time {
+            if !command.contains_key("maxTimeMS") {
+                command.insert("maxTimeMS", max_time.as_millis() as i64);
+            }
         }
+        command
     }
 
     fn fields_bsonc(&self) -> Option<bsonc::Bsonc> {
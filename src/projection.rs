@@ -0,0 +1,62 @@
+//! Build the fields document that selects which fields a find returns.
+
+use bson::Document;
+
+/// Builds a projection to use as the `fields` of `CommandAndFindOptions`.
+///
+/// ```
+/// # #[macro_use] extern crate bson;
+/// # extern crate mongo_driver;
+/// use mongo_driver::projection::Projection;
+///
+/// # fn main() {
+/// let fields = Projection::new()
+///     .include("name")
+///     .slice("comments", 5)
+///     .exclude("_id")
+///     .into_document();
+/// assert_eq!(doc! { "name": 1, "comments": { "$slice": 5 }, "_id": 0 }, fields);
+/// # }
+/// ```
+#[derive(Clone,Debug,Default)]
+pub struct Projection {
+    fields: Document
+}
+
+impl Projection {
+    /// Create an empty projection, which returns all fields.
+    pub fn new() -> Projection {
+        Projection {
+            fields: Document::new()
+        }
+    }
+
+    /// Return this field. Only `_id` and the included fields are returned once a field is included.
+    pub fn include(mut self, field: &str) -> Projection {
+        self.fields.insert(field, 1);
+        self
+    }
+
+    /// Leave out this field. Apart from `_id`, fields cannot be both included and excluded.
+    pub fn exclude(mut self, field: &str) -> Projection {
+        self.fields.insert(field, 0);
+        self
+    }
+
+    /// Return only the first `n` elements of this array field, or the last `n` if `n` is negative.
+    pub fn slice(mut self, field: &str, n: i32) -> Projection {
+        self.fields.insert(field, doc! { "$slice": n });
+        self
+    }
+
+    /// Get the fields document.
+    pub fn into_document(self) -> Document {
+        self.fields
+    }
+}
+
+impl From<Projection> for Document {
+    fn from(projection: Projection) -> Document {
+        projection.into_document()
+    }
+}
@@ -0,0 +1,112 @@
+//! Abstraction on top of the MongoDB connection read concern.
+
+use std::ffi::{CStr,CString};
+
+use crate::mongoc::bindings;
+
+use super::Result;
+use super::InvalidParamsError;
+use super::bsonc::Bsonc;
+
+/// Possible read concern levels.
+#[derive(Debug,PartialEq)]
+pub enum ReadConcernLevel {
+    /// Return the node's most recent data, regardless of whether it has been written to a majority of the replica set.
+    Local,
+    /// Return data that has been acknowledged by a majority of the replica set and cannot be rolled back.
+    Majority,
+    /// Return data that reflects all successful majority-acknowledged writes that completed before the read started.
+    Linearizable,
+    /// Like local, but might return orphaned documents on sharded clusters. Offers the lowest latency.
+    Available,
+    /// Return majority-committed data from a single point in time, only available in transactions.
+    Snapshot
+}
+
+fn read_concern_level_value(level: &ReadConcernLevel) -> &'static str {
+    match level {
+        &ReadConcernLevel::Local        => "local",
+        &ReadConcernLevel::Majority     => "majority",
+        &ReadConcernLevel::Linearizable => "linearizable",
+        &ReadConcernLevel::Available    => "available",
+        &ReadConcernLevel::Snapshot     => "snapshot"
+    }
+}
+
+/// This tells the server which level of isolation to use when reading data.
+/// The default, in which no level is set, leaves the choice to the server.
+pub struct ReadConcern {
+    inner: *mut bindings::mongoc_read_concern_t
+}
+
+impl ReadConcern {
+    /// Get the default read concern
+    pub fn default() -> ReadConcern {
+        let inner = unsafe { bindings::mongoc_read_concern_new() };
+        assert!(!inner.is_null());
+        ReadConcern { inner: inner }
+    }
+
+    /// Create a new read concern
+    pub fn new(level: ReadConcernLevel) -> ReadConcern {
+        let read_concern = Self::default();
+        let level_cstring = CString::new(read_concern_level_value(&level)).unwrap();
+        unsafe {
+            bindings::mongoc_read_concern_set_level(read_concern.inner, level_cstring.as_ptr());
+        }
+        read_concern
+    }
+
+    /// Get the level of this read concern, none if the server default is used.
+    pub fn level(&self) -> Option<ReadConcernLevel> {
+        assert!(!self.inner.is_null());
+        let level_ptr = unsafe { bindings::mongoc_read_concern_get_level(self.inner) };
+        if level_ptr.is_null() {
+            return None
+        }
+        let level = unsafe { CStr::from_ptr(level_ptr) };
+        match level.to_bytes() {
+            b"local"        => Some(ReadConcernLevel::Local),
+            b"majority"     => Some(ReadConcernLevel::Majority),
+            b"linearizable" => Some(ReadConcernLevel::Linearizable),
+            b"available"    => Some(ReadConcernLevel::Available),
+            b"snapshot"     => Some(ReadConcernLevel::Snapshot),
+            _               => None
+        }
+    }
+
+    /// Create a read concern from a copy of one owned by mongoc.
+    pub(crate) fn from_copy(inner: *const bindings::mongoc_read_concern_t) -> ReadConcern {
+        assert!(!inner.is_null());
+        let inner = unsafe { bindings::mongoc_read_concern_copy(inner) };
+        assert!(!inner.is_null());
+        ReadConcern { inner: inner }
+    }
+
+    #[doc(hidden)]
+    pub fn inner(&self) -> *const bindings::mongoc_read_concern_t {
+        assert!(!self.inner.is_null());
+        self.inner
+    }
+
+    /// Append this read concern to the opts of a command. Nothing is appended
+    /// if no level is set, so the server default applies.
+    pub(crate) fn append_to(&self, opts: &mut Bsonc) -> Result<()> {
+        assert!(!self.inner.is_null());
+        unsafe {
+            if bindings::mongoc_read_concern_append(self.inner, opts.mut_inner()) == 0 {
+                return Err(InvalidParamsError.into())
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ReadConcern {
+    fn drop(&mut self) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_read_concern_destroy(self.inner);
+        }
+    }
+}
@@ -1,8 +1,21 @@
 //! Abstraction on top of the MongoDB connection read prefences.
 
+use std::fmt;
+use std::time::Duration;
+
+use bson::{Bson,Document};
+
 use crate::mongoc::bindings;
 
+use super::Result;
+use super::InvalidParamsError;
+use super::bsonc::Bsonc;
+
+/// The smallest max staleness the server accepts.
+pub const MIN_MAX_STALENESS: Duration = Duration::from_secs(90);
+
 /// Describes how reads should be dispatched.
+#[derive(Debug,PartialEq)]
 pub enum ReadMode {
     /// Default mode. All operations read from the current replica set primary.
     Primary,
@@ -26,6 +39,16 @@ fn read_mode_value(read_mode: &ReadMode) -> bindings::mongoc_read_mode_t {
     }
 }
 
+fn read_mode_from_value(value: bindings::mongoc_read_mode_t) -> ReadMode {
+    match value {
+        bindings::MONGOC_READ_SECONDARY           => ReadMode::Secondary,
+        bindings::MONGOC_READ_PRIMARY_PREFERRED   => ReadMode::PrimaryPreferred,
+        bindings::MONGOC_READ_SECONDARY_PREFERRED => ReadMode::SecondaryPreferred,
+        bindings::MONGOC_READ_NEAREST             => ReadMode::Nearest,
+        _                                         => ReadMode::Primary
+    }
+}
+
 /// Provides an abstraction on top of the MongoDB connection read prefences.
 ///
 /// It allows for hinting to the driver which nodes in a replica set should be accessed first.
@@ -48,6 +71,81 @@ impl ReadPrefs {
         ReadPrefs::new(&ReadMode::Primary)
     }
 
+    /// Get the read mode.
+    pub fn mode(&self) -> ReadMode {
+        assert!(!self.inner.is_null());
+        read_mode_from_value(unsafe { bindings::mongoc_read_prefs_get_mode(self.inner) })
+    }
+
+    /// Get the tag sets used to select a member, for example `{"dc": "ny"}`.
+    pub fn tags(&self) -> Vec<Document> {
+        assert!(!self.inner.is_null());
+        let tags = unsafe { bindings::mongoc_read_prefs_get_tags(self.inner) };
+        match Bsonc::from_ptr(tags).as_document() {
+            Ok(document) => document.into_iter().filter_map(|(_, value)| match value {
+                Bson::Document(tag_set) => Some(tag_set),
+                _ => None
+            }).collect(),
+            Err(_) => Vec::new()
+        }
+    }
+
+    /// Set the maximum replication lag of a secondary before it is no longer selected for reads.
+    ///
+    /// Returns an `InvalidParamsError` if the staleness is below `MIN_MAX_STALENESS`,
+    /// mongoc would otherwise consider these read prefs invalid.
+    pub fn set_max_staleness(&mut self, staleness: Duration) -> Result<()> {
+        assert!(!self.inner.is_null());
+        if staleness < MIN_MAX_STALENESS {
+            return Err(InvalidParamsError.into())
+        }
+        unsafe {
+            bindings::mongoc_read_prefs_set_max_staleness_seconds(self.inner, staleness.as_secs() as i64);
+        }
+        Ok(())
+    }
+
+    /// Get the maximum replication lag, none if no maximum is set.
+    pub fn max_staleness(&self) -> Option<Duration> {
+        assert!(!self.inner.is_null());
+        let seconds = unsafe { bindings::mongoc_read_prefs_get_max_staleness_seconds(self.inner) };
+        if seconds < 0 {
+            None
+        } else {
+            Some(Duration::from_secs(seconds as u64))
+        }
+    }
+
+    /// Enable or configure hedged reads on a sharded cluster, for example with `{"enabled": true}`.
+    ///
+    /// Hedged reads require a non-primary read mode. Returns an `InvalidParamsError`
+    /// if the read mode is `Primary`.
+    pub fn set_hedge(&mut self, hedge: &Document) -> Result<()> {
+        assert!(!self.inner.is_null());
+        if unsafe { bindings::mongoc_read_prefs_get_mode(self.inner) } == bindings::MONGOC_READ_PRIMARY {
+            return Err(InvalidParamsError.into())
+        }
+        let hedge_bsonc = Bsonc::from_document(hedge)?;
+        unsafe {
+            bindings::mongoc_read_prefs_set_hedge(self.inner, hedge_bsonc.inner());
+        }
+        Ok(())
+    }
+
+    /// Whether mongoc considers these read prefs valid.
+    pub fn is_valid(&self) -> bool {
+        assert!(!self.inner.is_null());
+        unsafe { bindings::mongoc_read_prefs_is_valid(self.inner) == 1 }
+    }
+
+    /// Create read prefs from a copy of ones owned by mongoc.
+    pub(crate) fn from_copy(inner: *const bindings::mongoc_read_prefs_t) -> ReadPrefs {
+        assert!(!inner.is_null());
+        let inner = unsafe { bindings::mongoc_read_prefs_copy(inner) };
+        assert!(!inner.is_null());
+        ReadPrefs { inner: inner }
+    }
+
     #[doc(hidden)]
     pub fn inner(&self) -> *const bindings::mongoc_read_prefs_t {
         assert!(!self.inner.is_null());
@@ -61,6 +159,21 @@ impl ReadPrefs {
     }
 }
 
+impl Clone for ReadPrefs {
+    fn clone(&self) -> ReadPrefs {
+        ReadPrefs::from_copy(self.inner)
+    }
+}
+
+impl fmt::Debug for ReadPrefs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadPrefs")
+            .field("mode", &self.mode())
+            .field("tags", &self.tags())
+            .finish()
+    }
+}
+
 impl Drop for ReadPrefs {
     fn drop(&mut self) {
         assert!(!self.inner.is_null());
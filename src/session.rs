@@ -0,0 +1,244 @@
+//! Client sessions for causal consistency and multi-document transactions.
+
+use crate::mongoc::bindings;
+use bson::{Document,Timestamp};
+
+use super::Result;
+use super::{BsoncError,ReplyError};
+use super::bsonc::Bsonc;
+use super::client::Client;
+use super::read_concern::ReadConcern;
+use super::read_prefs::ReadPrefs;
+use super::write_concern::WriteConcern;
+
+/// Options to configure a client session.
+pub struct SessionOptions {
+    /// Whether reads in the session observe the writes made earlier in the session
+    pub causal_consistency: bool
+}
+
+impl SessionOptions {
+    /// Default options used if none are provided.
+    pub fn default() -> SessionOptions {
+        SessionOptions {
+            causal_consistency: true
+        }
+    }
+}
+
+/// Options to configure a transaction. Settings that are not set are inherited
+/// from the client.
+pub struct TransactionOptions {
+    /// Read concern for all reads in the transaction
+    pub read_concern:  Option<ReadConcern>,
+    /// Write concern used when committing or aborting the transaction
+    pub write_concern: Option<WriteConcern>,
+    /// Read prefs for all reads in the transaction, must be primary
+    pub read_prefs:    Option<ReadPrefs>
+}
+
+impl TransactionOptions {
+    /// Default options used if none are provided.
+    pub fn default() -> TransactionOptions {
+        TransactionOptions {
+            read_concern:  None,
+            write_concern: None,
+            read_prefs:    None
+        }
+    }
+}
+
+/// A session on a client, used to group operations so they are causally consistent.
+///
+/// A session is started by calling `start_session` on a `Client`. Pass it in the options of an
+/// operation to run the operation in the session. A session can only be used with collections
+/// of the client that started it, and by one thread at a time.
+pub struct ClientSession<'a> {
+    _client: &'a Client<'a>,
+    inner:   *mut bindings::mongoc_client_session_t
+}
+
+impl<'a> ClientSession<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        client: &'a Client<'a>,
+        inner:  *mut bindings::mongoc_client_session_t
+    ) -> ClientSession<'a> {
+        assert!(!inner.is_null());
+        ClientSession {
+            _client: client,
+            inner:   inner
+        }
+    }
+
+    /// Whether this session is causally consistent.
+    pub fn causal_consistency(&self) -> bool {
+        assert!(!self.inner.is_null());
+        unsafe {
+            let opts = bindings::mongoc_client_session_get_opts(self.inner);
+            bindings::mongoc_session_opts_get_causal_consistency(opts) == 1
+        }
+    }
+
+    /// Logical session id the server uses to identify this session.
+    pub fn lsid(&self) -> Result<Document> {
+        assert!(!self.inner.is_null());
+        let lsid_ptr = unsafe { bindings::mongoc_client_session_get_lsid(self.inner) };
+        Bsonc::from_ptr(lsid_ptr).as_document()
+    }
+
+    /// Operation time of the last operation in this session, none if no operation ran yet.
+    pub fn operation_time(&self) -> Option<Timestamp> {
+        assert!(!self.inner.is_null());
+        let mut time      = 0;
+        let mut increment = 0;
+        unsafe {
+            bindings::mongoc_client_session_get_operation_time(self.inner, &mut time, &mut increment);
+        }
+        if time == 0 && increment == 0 {
+            None
+        } else {
+            Some(Timestamp { time: time, increment: increment })
+        }
+    }
+
+    /// Highest cluster time seen in this session, none if no operation ran yet or the
+    /// deployment does not report cluster times.
+    pub fn cluster_time(&self) -> Option<Document> {
+        assert!(!self.inner.is_null());
+        let cluster_time_ptr = unsafe { bindings::mongoc_client_session_get_cluster_time(self.inner) };
+        if cluster_time_ptr.is_null() {
+            return None
+        }
+        Bsonc::from_ptr(cluster_time_ptr).as_document().ok()
+    }
+
+    /// Options document that runs a command in this session, to pass to a method that takes
+    /// raw opts such as `Client::read_command_with_opts`.
+    pub fn opts(&self) -> Result<Document> {
+        let mut opts = Bsonc::new();
+        self.append_to(&mut opts)?;
+        opts.as_document()
+    }
+
+    /// Start a multi-document transaction. All operations that are run in this session
+    /// join the transaction until it is committed or aborted.
+    ///
+    /// Transactions require a replica set or sharded cluster, starting one while another
+    /// transaction is in progress returns an error.
+    pub fn start_transaction(&mut self, options: Option<&TransactionOptions>) -> Result<()> {
+        assert!(!self.inner.is_null());
+
+        let default_options = TransactionOptions::default();
+        let options         = options.unwrap_or(&default_options);
+
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            let opts = bindings::mongoc_transaction_opts_new();
+            if let Some(ref read_concern) = options.read_concern {
+                bindings::mongoc_transaction_opts_set_read_concern(opts, read_concern.inner());
+            }
+            if let Some(ref write_concern) = options.write_concern {
+                bindings::mongoc_transaction_opts_set_write_concern(opts, write_concern.inner());
+            }
+            if let Some(ref read_prefs) = options.read_prefs {
+                bindings::mongoc_transaction_opts_set_read_prefs(opts, read_prefs.inner());
+            }
+            let success = bindings::mongoc_client_session_start_transaction(
+                self.inner,
+                opts,
+                error.mut_inner()
+            );
+            bindings::mongoc_transaction_opts_destroy(opts);
+            success
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Whether a transaction is in progress in this session.
+    pub fn in_transaction(&self) -> bool {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_client_session_in_transaction(self.inner) == 1
+        }
+    }
+
+    /// Commit the transaction in progress, making all its writes visible outside the session.
+    /// Returns the reply of the server.
+    ///
+    /// A commit that fails with an `UnknownTransactionCommitResult` error label can be retried.
+    pub fn commit_transaction(&mut self) -> Result<Document> {
+        assert!(!self.inner.is_null());
+
+        let mut reply = Bsonc::new();
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_client_session_commit_transaction(
+                self.inner,
+                reply.mut_inner(),
+                error.mut_inner()
+            )
+        };
+
+        if success == 1 {
+            reply.as_document()
+        } else {
+            Err(ReplyError::from_reply(error, &reply))
+        }
+    }
+
+    /// Abort the transaction in progress, discarding all its writes.
+    pub fn abort_transaction(&mut self) -> Result<()> {
+        assert!(!self.inner.is_null());
+
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_client_session_abort_transaction(self.inner, error.mut_inner())
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn inner(&self) -> *mut bindings::mongoc_client_session_t {
+        assert!(!self.inner.is_null());
+        self.inner
+    }
+
+    /// Append this session to the opts of an operation, so the operation runs in it.
+    pub(crate) fn append_to(&self, opts: &mut Bsonc) -> Result<()> {
+        assert!(!self.inner.is_null());
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_client_session_append(
+                self.inner,
+                opts.mut_inner(),
+                error.mut_inner()
+            )
+        };
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+}
+
+impl<'a> Drop for ClientSession<'a> {
+    fn drop(&mut self) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_client_session_destroy(self.inner);
+        }
+    }
+}
+
@@ -0,0 +1,56 @@
+//! Build the document that sets the order in which a find returns documents.
+
+use bson::Document;
+
+/// Builds a sort order to use as the `sort` of `CommandAndFindOptions`. Documents are
+/// sorted by the fields in the order they are added.
+///
+/// ```
+/// # #[macro_use] extern crate bson;
+/// # extern crate mongo_driver;
+/// use mongo_driver::sort::Sort;
+///
+/// # fn main() {
+/// let sort = Sort::new()
+///     .desc("created_at")
+///     .asc("name")
+///     .into_document();
+/// assert_eq!(doc! { "created_at": -1, "name": 1 }, sort);
+/// # }
+/// ```
+#[derive(Clone,Debug,Default)]
+pub struct Sort {
+    fields: Document
+}
+
+impl Sort {
+    /// Create an empty sort order.
+    pub fn new() -> Sort {
+        Sort {
+            fields: Document::new()
+        }
+    }
+
+    /// Sort by this field in ascending order.
+    pub fn asc(mut self, field: &str) -> Sort {
+        self.fields.insert(field, 1);
+        self
+    }
+
+    /// Sort by this field in descending order.
+    pub fn desc(mut self, field: &str) -> Sort {
+        self.fields.insert(field, -1);
+        self
+    }
+
+    /// Get the sort document.
+    pub fn into_document(self) -> Document {
+        self.fields
+    }
+}
+
+impl From<Sort> for Document {
+    fn from(sort: Sort) -> Document {
+        sort.into_document()
+    }
+}
@@ -1,22 +1,27 @@
 //! Abstraction on top of the MongoDB connection write concern.
 
+use std::fmt;
+use std::time::Duration;
+
 use crate::mongoc::bindings;
 
-/// Possible write concern levels, only default is supported at the moment.
+use super::Result;
+use super::InvalidParamsError;
+use super::bsonc::Bsonc;
+
+/// Possible write concern levels.
+#[derive(Debug,PartialEq)]
 pub enum WriteConcernLevel {
     /// By default, writes block awaiting acknowledgment from MongoDB. Acknowledged write concern allows clients to catch network, duplicate key, and other errors.
     Default,
-
-    // We'd like to support the following write concerns too at some point, pull request welcome:
-
-    // With this write concern, MongoDB does not acknowledge the receipt of write operation. Unacknowledged is similar to errors ignored; however, mongoc attempts to receive and handle network errors when possible.
-    // WriteUnacknowledged,
-    // Block until a write has been propagated to a majority of the nodes in the replica set.
-    // Majority,
-    // Block until a write has been propagated to at least n nodes in the replica set.
-    // AtLeastNumberOfNodes(u32),
-    // Block until the node receiving the write has committed the journal.
-    // Journal
+    /// With this write concern, MongoDB does not acknowledge the receipt of write operation. Unacknowledged is similar to errors ignored; however, mongoc attempts to receive and handle network errors when possible.
+    WriteUnacknowledged,
+    /// Block until a write has been propagated to a majority of the nodes in the replica set.
+    Majority,
+    /// Block until a write has been propagated to at least n nodes in the replica set.
+    AtLeastNumberOfNodes(u32),
+    /// Block until the node receiving the write has committed the journal.
+    Journal
 }
 
 /// This tells the driver what level of acknowledgment to await from the server.
@@ -32,9 +37,106 @@ impl WriteConcern {
     }
 
     /// Create a new write concern
-    pub fn new(_: WriteConcernLevel) -> WriteConcern {
+    pub fn new(level: WriteConcernLevel) -> WriteConcern {
         let inner = unsafe { bindings::mongoc_write_concern_new() };
         assert!(!inner.is_null());
+        unsafe {
+            match level {
+                WriteConcernLevel::Default => (),
+                WriteConcernLevel::WriteUnacknowledged => {
+                    bindings::mongoc_write_concern_set_w(inner, bindings::MONGOC_WRITE_CONCERN_W_UNACKNOWLEDGED)
+                },
+                WriteConcernLevel::Majority => {
+                    bindings::mongoc_write_concern_set_wmajority(inner, 0)
+                },
+                WriteConcernLevel::AtLeastNumberOfNodes(n) => {
+                    bindings::mongoc_write_concern_set_w(inner, n as i32)
+                },
+                WriteConcernLevel::Journal => {
+                    bindings::mongoc_write_concern_set_journal(inner, 1)
+                }
+            }
+        }
+        WriteConcern { inner: inner }
+    }
+
+    /// Get the level of this write concern
+    pub fn level(&self) -> WriteConcernLevel {
+        assert!(!self.inner.is_null());
+        unsafe {
+            if bindings::mongoc_write_concern_get_wmajority(self.inner) == 1 {
+                return WriteConcernLevel::Majority
+            }
+            match bindings::mongoc_write_concern_get_w(self.inner) {
+                bindings::MONGOC_WRITE_CONCERN_W_UNACKNOWLEDGED => WriteConcernLevel::WriteUnacknowledged,
+                w if w > 0 => WriteConcernLevel::AtLeastNumberOfNodes(w as u32),
+                _ if self.journal() => WriteConcernLevel::Journal,
+                _ => WriteConcernLevel::Default
+            }
+        }
+    }
+
+    /// Set how long the server waits for the write concern to be satisfied before returning
+    /// a timeout error. A timeout of zero waits indefinitely, which is the default.
+    ///
+    /// This only has effect for `Majority` and `AtLeastNumberOfNodes` with more than one node.
+    pub fn set_wtimeout(&mut self, timeout: Duration) {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_write_concern_set_wtimeout_int64(self.inner, timeout.as_millis() as i64);
+        }
+    }
+
+    /// Get the write concern timeout, zero if writes wait indefinitely.
+    pub fn wtimeout(&self) -> Duration {
+        assert!(!self.inner.is_null());
+        let wtimeout = unsafe { bindings::mongoc_write_concern_get_wtimeout_int64(self.inner) };
+        Duration::from_millis(wtimeout as u64)
+    }
+
+    /// Set whether writes should wait until they have been committed to the journal.
+    ///
+    /// Returns an `InvalidParamsError` when enabling the journal for an unacknowledged write
+    /// concern, the server cannot journal a write it does not acknowledge.
+    pub fn set_journal(&mut self, journal: bool) -> Result<()> {
+        assert!(!self.inner.is_null());
+        if journal && self.level() == WriteConcernLevel::WriteUnacknowledged {
+            return Err(InvalidParamsError.into())
+        }
+        unsafe {
+            bindings::mongoc_write_concern_set_journal(self.inner, journal as u8);
+        }
+        Ok(())
+    }
+
+    /// Whether writes wait until they have been committed to the journal.
+    pub fn journal(&self) -> bool {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_write_concern_get_journal(self.inner) == 1
+        }
+    }
+
+    /// Whether the journal was set explicitly, an explicit false is sent to the server
+    /// while an unset journal is left out.
+    fn journal_is_set(&self) -> bool {
+        assert!(!self.inner.is_null());
+        unsafe {
+            bindings::mongoc_write_concern_journal_is_set(self.inner) == 1
+        }
+    }
+
+    /// Get the raw `w` value, which is negative for the default and majority write concerns.
+    fn w(&self) -> i32 {
+        assert!(!self.inner.is_null());
+        unsafe { bindings::mongoc_write_concern_get_w(self.inner) }
+    }
+
+    /// Create a write concern from a copy of one owned by mongoc.
+    pub(crate) fn from_copy(inner: *const bindings::mongoc_write_concern_t) -> WriteConcern {
+        assert!(!inner.is_null());
+        let inner = unsafe { bindings::mongoc_write_concern_copy(inner) };
+        assert!(!inner.is_null());
         WriteConcern { inner: inner }
     }
 
@@ -43,6 +145,45 @@ impl WriteConcern {
         assert!(!self.inner.is_null());
         self.inner
     }
+
+    /// Append this write concern to the opts of a command. Nothing is appended
+    /// if this is the default write concern, so the server default applies.
+    pub(crate) fn append_to(&self, opts: &mut Bsonc) -> Result<()> {
+        assert!(!self.inner.is_null());
+        unsafe {
+            if bindings::mongoc_write_concern_is_default(self.inner) == 1 {
+                return Ok(())
+            }
+            if bindings::mongoc_write_concern_append(self.inner, opts.mut_inner()) == 0 {
+                return Err(InvalidParamsError.into())
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for WriteConcern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let w = match self.w() {
+            bindings::MONGOC_WRITE_CONCERN_W_DEFAULT => "default".to_string(),
+            bindings::MONGOC_WRITE_CONCERN_W_MAJORITY => "majority".to_string(),
+            w => w.to_string()
+        };
+        f.debug_struct("WriteConcern")
+            .field("w", &w)
+            .field("wtimeout", &self.wtimeout())
+            .field("journal", &self.journal())
+            .finish()
+    }
+}
+
+impl PartialEq for WriteConcern {
+    fn eq(&self, other: &WriteConcern) -> bool {
+        self.w() == other.w() &&
+            self.wtimeout() == other.wtimeout() &&
+            self.journal() == other.journal() &&
+            self.journal_is_set() == other.journal_is_set()
+    }
 }
 
 impl Drop for WriteConcern {
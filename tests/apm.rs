@@ -0,0 +1,39 @@
+extern crate mongo_driver;
+
+mod helpers;
+
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use mongo_driver::apm::ServerEvent;
+use mongo_driver::client::{ClientPool,Uri};
+
+#[test]
+fn test_server_heartbeat_events() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let mut pool = ClientPool::new(uri, None);
+
+    let (sender, receiver) = mpsc::channel();
+    let sender = Mutex::new(sender);
+    pool.set_server_event_handler(move |event| {
+        let _ = sender.lock().unwrap().send(event);
+    }).unwrap();
+
+    // The handler can only be set once
+    assert!(pool.set_server_event_handler(|_| ()).is_err());
+
+    // Servers are monitored once a client is used
+    let client = pool.pop();
+    client.ping().unwrap();
+
+    let mut heartbeat_succeeded = false;
+    while let Ok(event) = receiver.recv_timeout(Duration::from_secs(15)) {
+        if let ServerEvent::HeartbeatSucceeded(heartbeat) = event {
+            assert!(!heartbeat.host.is_empty());
+            heartbeat_succeeded = true;
+            break;
+        }
+    }
+    assert!(heartbeat_succeeded);
+}
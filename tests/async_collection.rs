@@ -0,0 +1,55 @@
+#![cfg(feature = "async")]
+
+extern crate bson;
+extern crate mongo_driver;
+extern crate tokio;
+
+mod helpers;
+
+use std::io;
+use std::sync::Arc;
+
+use bson::doc;
+
+use mongo_driver::async_collection::AsyncCollection;
+use mongo_driver::MongoError;
+use mongo_driver::client::{ClientPool,Uri};
+
+#[tokio::test]
+async fn test_async_collection() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = Arc::new(ClientPool::new(uri, None));
+    let collection = AsyncCollection::new(pool.clone(), "rust_driver_test", "async_items");
+    collection.remove(doc!{}).await.unwrap();
+
+    let result = collection.command_simple(doc!{"ping": 1}).await.unwrap();
+    assert!(result.contains_key("ok"));
+
+    let result = collection.insert_one(doc!{"key": 1}).await.unwrap();
+    assert!(result.inserted_id.as_object_id().is_some());
+    collection.insert_one(doc!{"key": 2}).await.unwrap();
+    assert_eq!(2, collection.count(doc!{}).await.unwrap());
+
+    collection.update(doc!{"key": 2}, doc!{"$set": {"key": 3}}).await.unwrap();
+    let found = collection.find_one(doc!{"key": 3}).await.unwrap().unwrap();
+    assert_eq!(Ok(3), found.get_i32("key"));
+    assert!(collection.find_one(doc!{"key": 2}).await.unwrap().is_none());
+
+    collection.remove(doc!{"key": 1}).await.unwrap();
+    let documents = collection.find(doc!{}).await.unwrap();
+    assert_eq!(1, documents.len());
+}
+
+#[tokio::test]
+async fn test_async_collection_pool_timeout() {
+    let uri        = Uri::new("mongodb://localhost:27017/?maxPoolSize=1&waitQueueTimeoutMS=100").unwrap();
+    let pool       = Arc::new(ClientPool::new(uri, None));
+    let collection = AsyncCollection::new(pool.clone(), "rust_driver_test", "async_pool_timeout");
+
+    // The pool is exhausted, so the operation times out instead of panicking
+    let _client = pool.pop();
+    match collection.count(doc!{}).await {
+        Err(MongoError::Io(error)) => assert_eq!(io::ErrorKind::TimedOut, error.kind()),
+        result => panic!("Unexpected result {:?}", result)
+    }
+}
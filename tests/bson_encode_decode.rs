@@ -40,7 +40,7 @@ fn test_bson_encode_decode() {
             bytes: vec![0, 1, 2, 3, 4]
         })
     };
-    collection.insert(&document, None).expect("Could not insert");
+    collection.insert_one(&document, None).expect("Could not insert");
 
     let found_document = collection.find(&doc!{}, None).unwrap().next().unwrap().unwrap();
 
@@ -7,6 +7,7 @@ use std::env;
 
 use bson::doc;
 use mongo_driver::client::{ClientPool,Uri};
+use mongo_driver::collection::BulkOperationOptions;
 
 #[test]
 fn test_execute_error() {
@@ -22,7 +23,130 @@ fn test_execute_error() {
     assert!(result.is_err());
 
     let error_message = format!("{:?}", result.err().unwrap());
-    assert_eq!(error_message, "BulkOperationError { error: MongoError (BsoncError: Command/CommandInvalidArg - Cannot do an empty bulk write), reply: Document({}) }");
+    assert_eq!(error_message, "BulkOperationError { error: MongoError (BsoncError: Command/CommandInvalidArg - Cannot do an empty bulk write), reply: Document({}), write_errors: [], write_concern_errors: [] }");
+}
+
+#[test]
+fn test_execute_write_errors() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection     = client.get_collection("rust_driver_test", "bulk_operation_write_errors");
+    collection.drop().unwrap_or(());
+
+    let mut options = BulkOperationOptions::default();
+    options.ordered = true;
+    let bulk_operation = collection.create_bulk_operation(Some(&options));
+    bulk_operation.insert(&doc! {"_id": 1}).unwrap();
+    bulk_operation.insert(&doc! {"_id": 2}).unwrap();
+    bulk_operation.insert(&doc! {"_id": 1}).unwrap();
+    bulk_operation.insert(&doc! {"_id": 3}).unwrap();
+
+    let error = bulk_operation.execute().err().unwrap();
+
+    assert_eq!(Ok(2), error.reply.get_i32("nInserted"));
+    assert_eq!(1, error.write_errors.len());
+    assert_eq!(2, error.write_errors[0].index);
+    assert_eq!(11000, error.write_errors[0].code);
+    assert!(!error.write_errors[0].errmsg.is_empty());
+    assert!(error.write_concern_errors.is_empty());
+}
+
+#[test]
+fn test_operations_with_opts() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection     = client.get_collection("rust_driver_test", "bulk_operation_with_opts");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc! {"name": "Alice", "scores": [1, 5, 9]}, None).unwrap();
+    collection.insert_one(&doc! {"name": "Bob", "scores": [2, 6]}, None).unwrap();
+
+    let case_insensitive = doc! {
+        "collation": {"locale": "en", "strength": 2}
+    };
+
+    let bulk_operation = collection.create_bulk_operation(None);
+    bulk_operation.update_one_with_opts(
+        &doc! {"name": "ALICE"},
+        &doc! {"$set": {"found": true}},
+        &case_insensitive
+    ).unwrap();
+    bulk_operation.update_with_opts(
+        &doc! {},
+        &doc! {"$set": {"scores.$[score]": 0}},
+        &doc! {"arrayFilters": [{"score": {"$gt": 4}}]}
+    ).unwrap();
+    bulk_operation.replace_one_with_opts(
+        &doc! {"name": "carol"},
+        &doc! {"name": "Carol"},
+        &doc! {"upsert": true}
+    ).unwrap();
+    bulk_operation.remove_one_with_opts(&doc! {"name": "BOB"}, &case_insensitive).unwrap();
+    let result = bulk_operation.execute().expect("Could not execute bulk operation");
+
+    assert_eq!(Ok(1), result.get_i32("nUpserted"));
+    assert_eq!(Ok(1), result.get_i32("nRemoved"));
+    let alice = collection.find(&doc! {"name": "Alice"}, None).unwrap().next().unwrap().unwrap();
+    assert_eq!(Ok(true), alice.get_bool("found"));
+    assert_eq!(
+        &vec![bson::Bson::Int32(1), bson::Bson::Int32(0), bson::Bson::Int32(0)],
+        alice.get_array("scores").unwrap()
+    );
+
+    let bulk_operation = collection.create_bulk_operation(None);
+    bulk_operation.remove_with_opts(&doc! {"name": "carol"}, &case_insensitive).unwrap();
+    bulk_operation.execute().expect("Could not execute bulk operation");
+    assert_eq!(1, collection.count_documents(&doc! {}, None).unwrap());
+}
+
+#[test]
+fn test_invalid_opts() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let collection     = client.get_collection("rust_driver_test", "bulk_operation_invalid_opts");
+
+    let bulk_operation = collection.create_bulk_operation(None);
+    assert!(bulk_operation.update_one_with_opts(
+        &doc! {},
+        &doc! {"$set": {"key": 1}},
+        &doc! {"upsert": "yes"}
+    ).is_err());
+}
+
+#[test]
+fn test_update_validation() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection     = client.get_collection("rust_driver_test", "bulk_operation_update_validation");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"key": 1}, None).unwrap();
+
+    let bulk_operation = collection.create_bulk_operation(None);
+
+    // Valid update
+    bulk_operation.update_one(&doc! {}, &doc! {"$set": {"key": 2}}, false).unwrap();
+
+    // Replacement documents are rejected
+    let replacement = doc! {"key": 3};
+    assert!(bulk_operation.update_one(&doc! {}, &replacement, false).is_err());
+    assert!(bulk_operation.update(&doc! {}, &replacement, false).is_err());
+    assert!(bulk_operation.update(&doc! {}, &doc! {}, false).is_err());
+
+    // Aggregation pipeline updates
+    let pipeline = doc! {
+        "0": {"$set": {"doubled": {"$multiply": ["$key", 2]}}}
+    };
+    bulk_operation.update(&doc! {}, &pipeline, false).unwrap();
+
+    bulk_operation.execute().expect("Could not execute bulk operation");
+
+    let document = collection.find(&doc! {}, None).unwrap().next().unwrap().unwrap();
+    assert_eq!(Ok(2), document.get_i32("key"));
+    assert_eq!(Ok(4), document.get_i32("doubled"));
 }
 
 #[test]
@@ -212,3 +336,35 @@ fn test_insert_remove_replace_update_extended() {
         assert_eq!(0, collection.count(&query, None).unwrap());
     }
 }
+
+#[test]
+fn test_bypass_document_validation() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let database       = client.get_database("rust_driver_test");
+    database.get_collection("bulk_operation_bypass_validation").drop().unwrap_or(());
+
+    let options = doc! {
+        "validator": {
+            "$jsonSchema": {
+                "bsonType": "object",
+                "required": ["name"]
+            }
+        }
+    };
+    let collection = database.create_collection("bulk_operation_bypass_validation", Some(&options)).unwrap();
+
+    let bulk_operation = collection.create_bulk_operation(None);
+    bulk_operation.insert(&doc! {"key": 1}).unwrap();
+    assert!(bulk_operation.execute().is_err());
+
+    let mut options = BulkOperationOptions::default();
+    options.bypass_document_validation = true;
+    let bulk_operation = collection.create_bulk_operation(Some(&options));
+    bulk_operation.insert(&doc! {"key": 1}).unwrap();
+    bulk_operation.insert(&doc! {"key": 2}).unwrap();
+    let reply = bulk_operation.execute().expect("Could not execute bulk operation");
+    assert_eq!(Ok(2), reply.get_i32("nInserted"));
+    assert_eq!(2, collection.count_documents(&doc!{}, None).unwrap());
+}
@@ -0,0 +1,194 @@
+extern crate bson;
+extern crate mongo_driver;
+
+mod helpers;
+
+use std::time::Duration;
+
+use bson::doc;
+
+use mongo_driver::change_stream::ChangeStreamOptions;
+use mongo_driver::client::{ClientPool,Uri};
+
+fn options_with_timeout() -> ChangeStreamOptions {
+    let mut options = ChangeStreamOptions::default();
+    options.timeout = Some(Duration::from_secs(5));
+    options
+}
+
+#[test]
+fn test_change_stream() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "change_stream");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"key": 0}, None).unwrap();
+
+    let mut change_stream = collection.watch(&[], Some(&options_with_timeout())).unwrap();
+
+    collection.insert_one(&doc! {"key": 1}, None).unwrap();
+
+    let event = change_stream.next().unwrap().unwrap();
+    assert_eq!(Ok("insert"), event.get_str("operationType"));
+    assert_eq!(Ok(1), event.get_document("fullDocument").unwrap().get_i32("key"));
+
+    let resume_token = change_stream.resume_token().unwrap();
+    assert!(!resume_token.is_empty());
+}
+
+#[test]
+fn test_change_stream_resume_after() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "change_stream_resume_after");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"key": 0}, None).unwrap();
+
+    let resume_token = {
+        let mut change_stream = collection.watch(&[], Some(&options_with_timeout())).unwrap();
+        collection.insert_one(&doc! {"key": 1}, None).unwrap();
+        collection.insert_one(&doc! {"key": 2}, None).unwrap();
+        change_stream.next().unwrap().unwrap();
+        change_stream.resume_token().unwrap()
+    };
+
+    let mut options = options_with_timeout();
+    options.resume_after = Some(resume_token);
+    let mut change_stream = collection.watch(&[], Some(&options)).unwrap();
+
+    let event = change_stream.next().unwrap().unwrap();
+    assert_eq!(Ok(2), event.get_document("fullDocument").unwrap().get_i32("key"));
+}
+
+#[test]
+fn test_change_stream_full_document() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "change_stream_full_document");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"_id": 1, "key": 0, "other": "value"}, None).unwrap();
+
+    let mut options = options_with_timeout();
+    options.full_document = Some("updateLookup".to_string());
+    let mut change_stream = collection.watch(&[], Some(&options)).unwrap();
+
+    collection.update(&doc! {"_id": 1}, &doc! {"$set": {"key": 1}}, None).unwrap();
+
+    let event = change_stream.next().unwrap().unwrap();
+    assert_eq!(Ok("update"), event.get_str("operationType"));
+    let full_document = event.get_document("fullDocument").unwrap();
+    assert_eq!(Ok(1), full_document.get_i32("key"));
+    assert_eq!(Ok("value"), full_document.get_str("other"));
+}
+
+#[test]
+fn test_database_change_stream() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_driver_test_database_change_stream");
+    for name in &["first", "second"] {
+        let mut collection = database.get_collection(*name);
+        collection.drop().unwrap_or(());
+        collection.insert_one(&doc! {"key": 0}, None).unwrap();
+    }
+
+    let mut change_stream = database.watch(&[], Some(&options_with_timeout())).unwrap();
+
+    database.get_collection("first").insert_one(&doc! {"key": 1}, None).unwrap();
+    database.get_collection("second").insert_one(&doc! {"key": 2}, None).unwrap();
+
+    let collections: Vec<String> = change_stream.by_ref().take(2)
+        .map(|event| event.unwrap().get_document("ns").unwrap().get_str("coll").unwrap().to_string())
+        .collect();
+    assert_eq!(vec!["first".to_string(), "second".to_string()], collections);
+}
+
+#[test]
+fn test_client_change_stream() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test_client_change_stream", "items");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"key": 0}, None).unwrap();
+
+    let pipeline = vec![doc! {"$match": {"ns.db": "rust_driver_test_client_change_stream"}}];
+    let mut change_stream = client.watch(&pipeline, Some(&options_with_timeout())).unwrap();
+
+    collection.insert_one(&doc! {"key": 1}, None).unwrap();
+
+    let event = change_stream.next().unwrap().unwrap();
+    assert_eq!(Ok("items"), event.get_document("ns").unwrap().get_str("coll"));
+}
+
+#[test]
+fn test_change_stream_pipeline() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "change_stream_pipeline");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"_id": 0}, None).unwrap();
+
+    let pipeline = vec![doc! {"$match": {"operationType": "insert"}}];
+    let mut change_stream = collection.watch(&pipeline, Some(&options_with_timeout())).unwrap();
+
+    collection.update(&doc! {"_id": 0}, &doc! {"$set": {"key": 1}}, None).unwrap();
+    collection.insert_one(&doc! {"_id": 1}, None).unwrap();
+
+    let event = change_stream.next().unwrap().unwrap();
+    assert_eq!(Ok("insert"), event.get_str("operationType"));
+    assert_eq!(Ok(1), event.get_document("documentKey").unwrap().get_i32("_id"));
+}
+
+#[test]
+fn test_change_stream_timeout() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "change_stream_timeout");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"key": 0}, None).unwrap();
+
+    let mut options = ChangeStreamOptions::default();
+    options.max_await_time = Some(Duration::from_millis(50));
+    options.timeout        = Some(Duration::from_millis(100));
+    let mut change_stream = collection.watch(&[], Some(&options)).unwrap();
+
+    assert!(change_stream.next().is_none());
+}
+
+#[test]
+fn test_change_stream_invalid_opts() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let collection = client.get_collection("rust_driver_test", "change_stream_invalid_opts");
+
+    let mut options = options_with_timeout();
+    options.full_document = Some("invalid".to_string());
+    assert!(collection.watch(&[], Some(&options)).is_err());
+}
+
+#[test]
+fn test_change_stream_ends_after_error() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "change_stream_ends_after_error");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! {"key": 0}, None).unwrap();
+
+    // Removing the resume token makes the stream fail once an event arrives
+    let pipeline = vec![doc! {"$project": {"_id": 0}}];
+    let mut change_stream = collection.watch(&pipeline, Some(&options_with_timeout())).unwrap();
+
+    collection.insert_one(&doc! {"key": 1}, None).unwrap();
+
+    assert!(change_stream.next().unwrap().is_err());
+    assert!(change_stream.next().is_none());
+}
@@ -4,12 +4,16 @@ extern crate mongo_driver;
 mod helpers;
 
 use std::env;
+use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
 use bson::doc;
 use mongo_driver::client::{ClientPool,SslOptions,Uri};
+use mongo_driver::read_prefs::{ReadMode,ReadPrefs};
 
 #[test]
 fn test_new_pool_pop_client_and_borrow_collection() {
@@ -75,6 +79,25 @@ fn test_new_pool_and_pop_client_in_threads() {
     guard2.join().unwrap();
 }
 
+#[test]
+fn test_with_client_in_scoped_threads() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    pool.set_max_size(2);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..4).map(|_| {
+            scope.spawn(|| pool.with_client(|client| client.ping()))
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+    });
+
+    // All clients were returned to the pool
+    assert!(pool.try_pop().is_some());
+}
+
 #[test]
 fn test_get_server_status() {
     let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
@@ -87,6 +110,28 @@ fn test_get_server_status() {
     assert!(status.contains_key("version"));
 }
 
+#[test]
+fn test_get_database_names() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    let names = client.get_database_names(None).unwrap();
+
+    assert!(names.iter().any(|name| name == "admin" || name == "local"));
+}
+
+#[test]
+fn test_get_database_names_with_filter() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    let names = client.get_database_names(Some(&doc! { "filter": { "name": "admin" } })).unwrap();
+
+    assert_eq!(vec!["admin".to_string()], names);
+}
+
 #[test]
 fn test_read_command_with_opts() {
     let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
@@ -100,7 +145,7 @@ fn test_read_command_with_opts() {
         "key_1": "Value 1",
         "key_2": "kācaṃ śaknomyattum; nopahinasti mām. \u{0}"
     };
-    collection.insert(&document, None).expect("Could not insert document");
+    collection.insert_one(&document, None).expect("Could not insert document");
 
     let status = client.read_command_with_opts(db_name,
                                                &doc!{"collStats": coll_name},
@@ -119,11 +164,50 @@ fn test_new_pool_with_ssl_options() {
         Some(PathBuf::from("./README.md")),
         Some(PathBuf::from("./README.md")),
         Some(PathBuf::from("./README.md")),
+        false,
         false
     ).expect("Ssl options not correct");
     ClientPool::new(uri, Some(ssl_options));
 }
 
+#[test]
+fn test_new_pool_with_ssl_options_allow_invalid_hostname() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let ssl_options = SslOptions::new(
+        None,
+        None,
+        Some(PathBuf::from("./README.md")),
+        None,
+        None,
+        false,
+        true
+    ).expect("Ssl options not correct");
+    let cloned_ssl_options = ssl_options.clone();
+    ClientPool::new(uri.clone(), Some(ssl_options));
+    ClientPool::new(uri, Some(cloned_ssl_options));
+}
+
+#[test]
+fn test_ssl_options_from_pem_bytes() {
+    let pem = b"-----BEGIN CERTIFICATE-----\nMIIBszCCAVmgAwIBAgIUZmFrZQ==\n-----END CERTIFICATE-----\n";
+    let ssl_options = SslOptions::from_pem_bytes(pem, None, Some(pem), false, false).unwrap();
+
+    let pem_file = ssl_options.pem_file().unwrap().clone();
+    let ca_file = ssl_options.ca_file().unwrap().clone();
+    assert_eq!(&pem[..], &fs::read(&pem_file).unwrap()[..]);
+    assert_eq!(&pem[..], &fs::read(&ca_file).unwrap()[..]);
+
+    // Clones keep the files around
+    let cloned_ssl_options = ssl_options.clone();
+    drop(ssl_options);
+    assert!(pem_file.exists());
+
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    ClientPool::new(uri, Some(cloned_ssl_options));
+    assert!(!pem_file.exists());
+    assert!(!ca_file.exists());
+}
+
 #[test]
 fn test_ssl_options_nonexistent_file() {
     assert!(SslOptions::new(
@@ -132,6 +216,7 @@ fn test_ssl_options_nonexistent_file() {
         Some(PathBuf::from("/tmp/aaaaa.aa")),
         Some(PathBuf::from("/tmp/aaaaa.aa")),
         Some(PathBuf::from("/tmp/aaaaa.aa")),
+        false,
         false
     ).is_err());
 }
@@ -155,6 +240,7 @@ fn test_ssl_connection_success() {
         Some(ca_file),
         None,
         None,
+        false,
         false
     ).unwrap();
 
@@ -165,3 +251,196 @@ fn test_ssl_connection_success() {
     let result = database.command_simple(doc!{"ping": 1}, None).unwrap();
     assert!(result.contains_key("ok"));
 }
+
+#[test]
+fn test_srv_connection() {
+    let uri_string = match env::var("MONGO_RUST_DRIVER_SRV_URI") {
+        Ok(uri_string) => uri_string,
+        Err(_) => return
+    };
+
+    let uri = Uri::new(uri_string).unwrap();
+    assert!(uri.get_srv_hostname().is_some());
+    assert!(uri.is_tls());
+
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+    client.ping().unwrap();
+}
+
+#[test]
+fn test_command_simple() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    let build_info = client.command_simple("admin", doc!{"buildInfo": 1}, None).unwrap();
+    assert!(build_info.contains_key("version"));
+
+    assert!(client.command_simple("admin", doc!{"unknownCommand": 1}, None).is_err());
+}
+
+#[test]
+fn test_set_appname() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    pool.set_appname("rust_driver_test").unwrap();
+
+    // The name can only be set once
+    assert!(pool.set_appname("other").is_err());
+
+    let client = pool.pop();
+    assert!(client.ping().is_ok());
+}
+
+#[test]
+fn test_appname_from_uri() {
+    let uri = Uri::new("mongodb://localhost:27017/?appName=rust_driver_uri_test").unwrap();
+    let pool = ClientPool::new(uri, None);
+    assert_eq!("rust_driver_uri_test", pool.get_uri().get_appname().unwrap());
+
+    let client = pool.pop();
+    assert!(client.ping().is_ok());
+}
+
+#[test]
+fn test_ping() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    assert!(client.ping().is_ok());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_compression() {
+    let connection_string = helpers::mongodb_test_connection_string();
+    let separator = if connection_string.contains('?') {
+        "&"
+    } else if connection_string.ends_with('/') {
+        "?"
+    } else {
+        "/?"
+    };
+    let uri = Uri::new(format!("{}{}compressors=zstd", connection_string, separator)).unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    assert!(client.ping().is_ok());
+    assert!(client.get_server_status(None).is_ok());
+}
+
+#[test]
+fn test_ping_unreachable_server() {
+    let uri = Uri::new("mongodb://localhost:27999/?serverSelectionTimeoutMS=100").unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    assert!(client.ping().is_err());
+}
+
+#[test]
+fn test_server_rtt() {
+    let uri = Uri::new("mongodb://localhost:27017/").unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    // Perform an operation so the server is discovered
+    client.get_server_status(None).unwrap();
+
+    assert!(client.server_rtt("localhost:27017").is_some());
+    assert!(client.server_rtt("unknown:27017").is_none());
+}
+
+#[test]
+fn test_max_bson_and_message_size() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    let hello = client.command_simple("admin", doc! { "hello": 1 }, None).unwrap();
+    assert_eq!(hello.get_i32("maxBsonObjectSize").unwrap() as u32, client.max_bson_size());
+    assert_eq!(hello.get_i32("maxMessageSizeBytes").unwrap() as u32, client.max_message_size());
+    assert_eq!(16 * 1024 * 1024, client.max_bson_size());
+}
+
+#[test]
+fn test_select_server() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    let description = client.select_server(true, None).unwrap();
+    assert!(description.server_type.is_writable());
+    assert!(!description.host.is_empty());
+    assert!(description.round_trip_time.is_some());
+
+    let description = client.select_server(false, Some(&ReadPrefs::new(&ReadMode::PrimaryPreferred))).unwrap();
+    assert!(description.server_type.is_writable());
+}
+
+#[test]
+fn test_select_server_unreachable() {
+    let uri = Uri::new("mongodb://localhost:27999/?serverSelectionTimeoutMS=100").unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    assert!(client.select_server(true, None).is_err());
+}
+
+#[test]
+fn test_pool_wait_queue_timeout() {
+    let uri = Uri::new("mongodb://localhost:27017/?maxPoolSize=1&waitQueueTimeoutMS=100").unwrap();
+    let pool = ClientPool::new(uri, None);
+    let client = pool.try_pop_wait();
+    assert!(client.is_some());
+
+    // The pool is exhausted, so popping another client times out
+    assert!(pool.try_pop_wait().is_none());
+
+    // Once the client is returned it can be popped again
+    drop(client);
+    assert!(pool.try_pop_wait().is_some());
+}
+
+#[test]
+fn test_pool_max_size() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = Arc::new(ClientPool::new(uri, None));
+    pool.set_max_size(2);
+    pool.set_min_size(1);
+
+    let first_client = pool.pop();
+    let _second_client = pool.pop();
+
+    // The pool is exhausted, so popping a third client blocks
+    let (sender, receiver) = mpsc::channel();
+    let cloned_pool = pool.clone();
+    let handle = thread::spawn(move || {
+        cloned_pool.pop();
+        sender.send(()).unwrap();
+    });
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+    // Returning a client to the pool unblocks it
+    drop(first_client);
+    assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_pool_try_pop() {
+    let uri = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool = ClientPool::new(uri, None);
+    pool.set_max_size(1);
+
+    let client = pool.try_pop();
+    assert!(client.is_some());
+
+    // The pool is exhausted while the client is held
+    assert!(pool.try_pop().is_none());
+
+    drop(client);
+    assert!(pool.try_pop().is_some());
+}
@@ -1,14 +1,24 @@
 extern crate bson;
 extern crate mongo_driver;
+#[macro_use]
+extern crate serde_derive;
 
 mod helpers;
 
-use bson::doc;
+use std::time::{Duration,Instant};
 
-use mongo_driver::CommandAndFindOptions;
-use mongo_driver::collection::{CountOptions,FindAndModifyOperation};
+use bson::{doc,Bson};
+
+use mongo_driver::{CommandAndFindOptions,MongoError,MongoErrorCode};
+use mongo_driver::collection::{CountOptions,FindAndModifyOperation,FindAndModifyOptions,InsertManyOptions,InsertOneResult,InsertOptions,ReplaceOptions,TextSearchOptions,UpdateOptions,UpdateResult};
 use mongo_driver::client::{ClientPool,Uri};
 use mongo_driver::flags;
+use mongo_driver::index::IndexModel;
+use mongo_driver::projection::Projection;
+use mongo_driver::read_concern::{ReadConcern,ReadConcernLevel};
+use mongo_driver::read_prefs::{ReadMode,ReadPrefs};
+use mongo_driver::sort::Sort;
+use mongo_driver::write_concern::{WriteConcern,WriteConcernLevel};
 
 #[test]
 fn test_aggregate() {
@@ -19,7 +29,7 @@ fn test_aggregate() {
     collection.drop().unwrap_or(());
 
     for _ in 0..5 {
-        collection.insert(&doc!{"key": 1}, None).expect("Could not insert");
+        collection.insert_one(&doc!{"key": 1}, None).expect("Could not insert");
     }
 
     let pipeline = doc!{
@@ -38,6 +48,38 @@ fn test_aggregate() {
     assert_eq!(Ok(5), total.get_i32("total"));
 }
 
+#[test]
+fn test_aggregate_to_collection() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "aggregate_source");
+    let mut output = client.get_collection("rust_driver_test", "aggregate_output");
+    collection.drop().unwrap_or(());
+    output.drop().unwrap_or(());
+
+    for key in 0..5 {
+        collection.insert_one(&doc!{"key": key}, None).expect("Could not insert");
+    }
+
+    let pipeline = doc!{
+        "pipeline": [
+            { "$match": { "key": { "$gte": 2 } } },
+            { "$out": "aggregate_output" }
+        ]
+    };
+    collection.aggregate_to_collection(&pipeline, None).unwrap();
+    assert_eq!(3, output.count(&doc!{}, None).unwrap());
+
+    // An invalid write stage is reported
+    let pipeline = doc!{
+        "pipeline": [
+            { "$out": "" }
+        ]
+    };
+    assert!(collection.aggregate_to_collection(&pipeline, None).is_err());
+}
+
 #[test]
 fn test_command() {
     let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
@@ -64,6 +106,179 @@ fn test_command_simple() {
     assert!(result.contains_key("ok"));
 }
 
+#[test]
+fn test_command_simple_and_find_and_modify_error_reply() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "error_reply");
+    collection.drop().unwrap_or(());
+
+    match collection.command_simple(doc! { "unknownCommand": 1 }, None) {
+        Err(MongoError::Reply(error)) => {
+            assert!(!error.reply.is_empty());
+            assert!(error.reply.contains_key("errmsg"));
+        },
+        result => panic!("Unexpected result {:?}", result)
+    }
+
+    let update = doc! { "$unknownOperator": { "key": 1 } };
+    match collection.find_and_modify(&doc! {}, FindAndModifyOperation::Upsert(&update), None) {
+        Err(MongoError::Reply(error)) => assert!(!error.reply.is_empty()),
+        result => panic!("Unexpected result {:?}", result)
+    }
+}
+
+#[test]
+fn test_count_documents() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "count_documents");
+    collection.drop().unwrap_or(());
+
+    assert_eq!(0, collection.count_documents(&doc!{}, None).unwrap());
+
+    for i in 0..7 {
+        collection.insert_one(&doc!{"key": i % 2}, None).unwrap();
+    }
+
+    assert_eq!(7, collection.count_documents(&doc!{}, None).unwrap());
+    assert_eq!(4, collection.count_documents(&doc!{"key": 0}, None).unwrap());
+
+    let mut count_options = CountOptions::default();
+    count_options.skip  = 2;
+    count_options.limit = 3;
+    assert_eq!(3, collection.count_documents(&doc!{}, Some(&count_options)).unwrap());
+}
+
+#[test]
+fn test_collation() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "collation");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{ "name": "Café" }, None).unwrap();
+    collection.insert_one(&doc!{ "name": "Tea" }, None).unwrap();
+
+    let query = doc!{ "name": "cafe" };
+    assert_eq!(0, collection.find(&query, None).unwrap().count());
+    assert_eq!(0, collection.count_documents(&query, None).unwrap());
+
+    // Strength 1 ignores case and diacritics
+    let collation = doc!{ "locale": "en", "strength": 1 };
+
+    let mut find_options = CommandAndFindOptions::default();
+    find_options.collation = Some(collation.clone());
+    let documents = collection.find(&query, Some(&find_options)).unwrap()
+        .collect::<mongo_driver::Result<Vec<_>>>().unwrap();
+    assert_eq!(1, documents.len());
+    assert_eq!(Ok("Café"), documents[0].get_str("name"));
+
+    let mut count_options = CountOptions::default();
+    count_options.collation = Some(collation);
+    assert_eq!(1, collection.count(&query, Some(&count_options)).unwrap());
+    assert_eq!(1, collection.count_documents(&query, Some(&count_options)).unwrap());
+}
+
+#[test]
+fn test_create_index() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "create_index");
+    collection.drop().unwrap_or(());
+
+    let model = IndexModel {
+        keys:    doc!{"email": 1},
+        options: Some(doc!{"unique": true})
+    };
+    assert_eq!("email_1", collection.create_index(&model).unwrap());
+
+    collection.insert_one(&doc!{"email": "a@example.com"}, None).unwrap();
+    assert!(collection.insert_one(&doc!{"email": "a@example.com"}, None).is_err());
+}
+
+#[test]
+fn test_create_partial_index() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "create_partial_index");
+    collection.drop().unwrap_or(());
+
+    let model = IndexModel {
+        keys:    doc!{"email": 1, "active": -1},
+        options: Some(doc!{
+            "name": "active_email",
+            "unique": true,
+            "partialFilterExpression": {"active": true}
+        })
+    };
+    assert_eq!("active_email", collection.create_index(&model).unwrap());
+
+    collection.insert_one(&doc!{"email": "a@example.com", "active": false}, None).unwrap();
+    collection.insert_one(&doc!{"email": "a@example.com", "active": false}, None).unwrap();
+    collection.insert_one(&doc!{"email": "a@example.com", "active": true}, None).unwrap();
+    assert!(collection.insert_one(&doc!{"email": "a@example.com", "active": true}, None).is_err());
+}
+
+#[test]
+fn test_drop_index() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "drop_index");
+    collection.drop().unwrap_or(());
+
+    let name = collection.create_index(&IndexModel::new(doc!{"email": 1})).unwrap();
+    collection.drop_index(&name).unwrap();
+
+    let index_names: Vec<String> = collection.list_indexes().unwrap()
+        .map(|index| index.unwrap().get_str("name").unwrap().to_string())
+        .collect();
+    assert_eq!(vec!["_id_".to_string()], index_names);
+
+    assert!(collection.drop_index(&name).is_err());
+}
+
+#[test]
+fn test_drop_all_indexes() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "drop_all_indexes");
+    collection.drop().unwrap_or(());
+
+    collection.create_index(&IndexModel::new(doc!{"email": 1})).unwrap();
+    collection.create_index(&IndexModel::new(doc!{"name": 1})).unwrap();
+    assert_eq!(3, collection.list_indexes().unwrap().count());
+
+    collection.drop_all_indexes().unwrap();
+    assert_eq!(1, collection.list_indexes().unwrap().count());
+}
+
+#[test]
+fn test_distinct() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "distinct");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"k": 1}, None).unwrap();
+    collection.insert_one(&doc!{"k": 2}, None).unwrap();
+    collection.insert_one(&doc!{"k": 2}, None).unwrap();
+
+    let mut values = collection.distinct("k", &doc!{}, None).unwrap();
+    values.sort_by_key(|value| value.as_i32());
+    assert_eq!(vec![Bson::Int32(1), Bson::Int32(2)], values);
+
+    assert!(collection.distinct("k", &doc!{"k": 3}, None).unwrap().is_empty());
+}
+
 #[test]
 fn test_mutation_and_finding() {
     let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
@@ -79,7 +294,7 @@ fn test_mutation_and_finding() {
         "key_1": "Value 1",
         "key_2": "kācaṃ śaknomyattum; nopahinasti mām. \u{0}"
     };
-    collection.insert(&document, None).expect("Could not insert document");
+    collection.insert_one(&document, None).expect("Could not insert document");
     {
         let found_document = collection.find(&document, None).unwrap().next().unwrap().unwrap();
         assert_eq!(
@@ -95,7 +310,7 @@ fn test_mutation_and_finding() {
     let second_document = doc! {
         "key_1": "Value 3"
     };
-    collection.insert(&second_document, None).expect("Could not insert");
+    collection.insert_one(&second_document, None).expect("Could not insert");
 
     let query = doc!{};
 
@@ -165,7 +380,15 @@ fn test_mutation_and_finding() {
             limit:       0,
             batch_size:  0,
             fields:      Some(doc! { "key_1": true }),
-            read_prefs:  None
+            read_prefs:  None,
+            read_concern: None,
+            session:     None,
+            collation:   None,
+            hint:        None,
+            sort:        None,
+            max_time:    None,
+            comment:     None,
+            utf8_lossy:  true
         };
 
         // Query a couple of times to make sure the C driver keeps
@@ -231,6 +454,756 @@ fn test_find_and_modify() {
     assert_eq!(0, collection.count(&query, None).unwrap());
 }
 
+#[test]
+fn test_find_one_and_update() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_one_and_update");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"_id": 1, "value": 1}, None).unwrap();
+
+    let update   = doc!{"$inc": {"value": 1}};
+    let document = collection.find_one_and_update(&doc!{"_id": 1}, &update, None).unwrap();
+    assert_eq!(Some(doc!{"_id": 1, "value": 1}), document);
+
+    let mut options = FindAndModifyOptions::default();
+    options.new = true;
+    let document = collection.find_one_and_update(&doc!{"_id": 1}, &update, Some(&options)).unwrap();
+    assert_eq!(Some(doc!{"_id": 1, "value": 3}), document);
+
+    assert_eq!(None, collection.find_one_and_update(&doc!{"_id": 2}, &update, None).unwrap());
+
+    // A replacement is not an update
+    let replacement = doc!{"value": 4};
+    assert!(collection.find_one_and_update(&doc!{"_id": 1}, &replacement, None).is_err());
+}
+
+#[test]
+fn test_find_one_and_replace() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_one_and_replace");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"_id": 1, "value": 1}, None).unwrap();
+
+    let replacement = doc!{"other": 2};
+    let document    = collection.find_one_and_replace(&doc!{"_id": 1}, &replacement, None).unwrap();
+    assert_eq!(Some(doc!{"_id": 1, "value": 1}), document);
+
+    let mut options = FindAndModifyOptions::default();
+    options.new = true;
+    let replacement = doc!{"other": 3};
+    let document    = collection.find_one_and_replace(&doc!{"_id": 1}, &replacement, Some(&options)).unwrap();
+    assert_eq!(Some(doc!{"_id": 1, "other": 3}), document);
+
+    assert_eq!(None, collection.find_one_and_replace(&doc!{"_id": 2}, &replacement, None).unwrap());
+
+    // An update is not a replacement
+    let update = doc!{"$set": {"other": 4}};
+    assert!(collection.find_one_and_replace(&doc!{"_id": 1}, &update, None).is_err());
+}
+
+#[test]
+fn test_find_one_and_delete() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_one_and_delete");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"_id": 1, "value": 1}, None).unwrap();
+
+    // There is no new version of a deleted document
+    let mut options = FindAndModifyOptions::default();
+    options.new = true;
+    assert!(collection.find_one_and_delete(&doc!{"_id": 1}, Some(&options)).is_err());
+
+    let document = collection.find_one_and_delete(&doc!{"_id": 1}, None).unwrap();
+    assert_eq!(Some(doc!{"_id": 1, "value": 1}), document);
+    assert_eq!(0, collection.count_documents(&doc!{}, None).unwrap());
+
+    assert_eq!(None, collection.find_one_and_delete(&doc!{"_id": 1}, None).unwrap());
+}
+
+#[test]
+fn test_find_and_modify_with_array_filters() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_and_modify_array_filters");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"_id": 1, "grades": [80, 95, 90]}, None).unwrap();
+
+    let mut options = FindAndModifyOptions::default();
+    options.new           = true;
+    options.write_concern = WriteConcern::new(WriteConcernLevel::Majority);
+    options.collation     = Some(doc!{"locale": "en"});
+    options.array_filters = Some(vec![doc!{"grade": {"$gte": 90}}]);
+    let reply = collection.find_and_modify(
+        &doc!{"_id": 1},
+        FindAndModifyOperation::Update(&doc!{"$set": {"grades.$[grade]": 100}}),
+        Some(&options)
+    ).expect("Could not find and modify");
+
+    let expected = Bson::Array(vec![Bson::Int32(80), Bson::Int32(100), Bson::Int32(100)]);
+    assert_eq!(Some(&expected), reply.get_document("value").unwrap().get("grades"));
+}
+
+#[test]
+fn test_bypass_document_validation() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_driver_test");
+    database.get_collection("bypass_validation").drop().unwrap_or(());
+
+    let options = doc! {
+        "validator": {
+            "$jsonSchema": {
+                "bsonType": "object",
+                "required": ["name"]
+            }
+        }
+    };
+    let collection = database.create_collection("bypass_validation", Some(&options)).unwrap();
+
+    // Insert
+    let invalid_document = doc! { "key_1": "Value 1" };
+    assert!(collection.insert_one(&invalid_document, None).is_err());
+
+    let mut insert_options = InsertOptions::default();
+    insert_options.bypass_document_validation = true;
+    collection.insert_one(&invalid_document, Some(&insert_options)).expect("Could not insert");
+    assert_eq!(1, collection.count(&invalid_document, None).unwrap());
+
+    // Update
+    let update = doc! { "$set": {"key_1": "Value 2"} };
+    assert!(collection.update(&invalid_document, &update, None).is_err());
+
+    let mut update_options = UpdateOptions::default();
+    update_options.bypass_document_validation = true;
+    collection.update(&invalid_document, &update, Some(&update_options)).expect("Could not update");
+    assert_eq!(1, collection.count(&doc!{"key_1": "Value 2"}, None).unwrap());
+
+    // Replace
+    let replacement = doc! { "key_1": "Value 2", "replaced": true };
+    assert!(collection.replace_one(&doc!{"key_1": "Value 2"}, &replacement, None).is_err());
+
+    let mut replace_options = ReplaceOptions::default();
+    replace_options.bypass_document_validation = true;
+    let result = collection.replace_one(&doc!{"key_1": "Value 2"}, &replacement, Some(&replace_options))
+        .expect("Could not replace");
+    assert_eq!(1, result.modified_count);
+    assert_eq!(1, collection.count(&replacement, None).unwrap());
+
+    // Find and modify
+    let query = doc! { "key_1": "Value 2" };
+    let update = doc! { "$set": {"key_1": "Value 3"} };
+    assert!(collection.find_and_modify(&query, FindAndModifyOperation::Update(&update), None).is_err());
+
+    let mut find_and_modify_options = FindAndModifyOptions::default();
+    find_and_modify_options.bypass_document_validation = true;
+    collection.find_and_modify(
+        &query,
+        FindAndModifyOperation::Update(&update),
+        Some(&find_and_modify_options)
+    ).expect("Could not find and modify");
+    assert_eq!(1, collection.count(&doc!{"key_1": "Value 3"}, None).unwrap());
+}
+
+#[test]
+fn test_insert_duplicate_key() {
+    let uri            = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool           = ClientPool::new(uri, None);
+    let client         = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "duplicate_key");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc! { "_id": 1 }, None).expect("Could not insert");
+    let error = collection.insert_one(&doc! { "_id": 1 }, None).unwrap_err();
+    let error = error.bsonc_error().expect("Expected an error from the C driver");
+    assert_eq!(MongoErrorCode::DuplicateKey, error.code());
+    assert_eq!(11000, error.server_code());
+}
+
+#[test]
+fn test_text_search() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_driver_test");
+    let mut collection = database.get_collection("text_search");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"content": "The quick brown fox", "kind": "animal"}, None).unwrap();
+    collection.insert_one(&doc!{"content": "A lazy dog", "kind": "animal"}, None).unwrap();
+    collection.insert_one(&doc!{"content": "A quick lunch", "kind": "food"}, None).unwrap();
+
+    // Searching without a text index fails
+    let result = collection.text_search("quick", None, None).unwrap().next().unwrap();
+    assert!(result.is_err());
+
+    database.command_simple(doc!{
+        "createIndexes": "text_search",
+        "indexes": [{"key": {"content": "text"}, "name": "content_text"}]
+    }, None).unwrap();
+
+    // Search without filter or score
+    let documents = collection.text_search("quick", None, None).unwrap()
+        .collect::<mongo_driver::Result<Vec<_>>>().unwrap();
+    assert_eq!(2, documents.len());
+    assert!(!documents[0].contains_key("score"));
+
+    // Search with a filter and the score
+    let mut options = TextSearchOptions::default();
+    options.score_field = Some("score".to_string());
+    let documents = collection.text_search("quick", Some(&doc!{"kind": "food"}), Some(&options)).unwrap()
+        .collect::<mongo_driver::Result<Vec<_>>>().unwrap();
+    assert_eq!(1, documents.len());
+    assert_eq!(Ok("A quick lunch"), documents[0].get_str("content"));
+    assert!(documents[0].get_f64("score").unwrap() > 0.0);
+}
+
+#[test]
+fn test_find_options() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_options");
+    collection.drop().unwrap_or(());
+
+    for i in 0..10 {
+        collection.insert_one(&doc!{ "position": i }, None).unwrap();
+    }
+    collection.insert_one(&doc!{ "other": true }, None).unwrap();
+
+    // Skip and limit
+    let mut options = CommandAndFindOptions::default();
+    options.skip  = 2;
+    options.limit = 3;
+    let query = doc!{ "$query": { "position": { "$exists": true } }, "$orderby": { "position": 1 } };
+    let positions = collection.find(&query, Some(&options)).unwrap()
+        .map(|document| document.unwrap().get_i32("position").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![2, 3, 4], positions);
+
+    // A sparse index only contains documents that have the field, so using it
+    // as a hint leaves out the other document.
+    let model = IndexModel {
+        keys:    doc!{ "position": 1 },
+        options: Some(doc!{ "name": "position_sparse", "sparse": true })
+    };
+    collection.create_index(&model).unwrap();
+    assert_eq!(11, collection.find(&doc!{}, None).unwrap().count());
+    let query = doc!{ "$query": {}, "$hint": "position_sparse" };
+    assert_eq!(10, collection.find(&query, None).unwrap().count());
+}
+
+#[test]
+fn test_find_with_hint() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_with_hint");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{ "key": 1 }, None).unwrap();
+    collection.insert_one(&doc!{ "other": 1 }, None).unwrap();
+    let model = IndexModel {
+        keys:    doc!{ "key": 1 },
+        options: Some(doc!{ "name": "key_sparse", "sparse": true })
+    };
+    collection.create_index(&model).unwrap();
+
+    // By name and by key document, the sparse index leaves out the other document
+    let mut options = CommandAndFindOptions::default();
+    options.hint = Some(Bson::String("key_sparse".to_string()));
+    assert_eq!(1, collection.find(&doc!{}, Some(&options)).unwrap().count());
+    options.hint = Some(Bson::Document(doc!{ "key": 1 }));
+    assert_eq!(1, collection.find(&doc!{}, Some(&options)).unwrap().count());
+
+    // An index that doesn't exist is a server error
+    options.hint = Some(Bson::String("missing".to_string()));
+    let result = collection.find(&doc!{}, Some(&options)).unwrap().next().unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_find_with_projection() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_with_projection");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{ "name": "bolt", "quantity": 20, "tags": ["steel", "m6", "din933"] }, None).unwrap();
+
+    let fields = Projection::new()
+        .include("name")
+        .include("quantity")
+        .exclude("_id");
+    let options = CommandAndFindOptions::with_fields(fields.into());
+    let document = collection.find(&doc!{}, Some(&options)).unwrap().next().unwrap().unwrap();
+    assert_eq!(doc!{ "name": "bolt", "quantity": 20 }, document);
+
+    let options = CommandAndFindOptions::with_fields(Projection::new().slice("tags", -2).into());
+    let document = collection.find(&doc!{}, Some(&options)).unwrap().next().unwrap().unwrap();
+    assert_eq!(4, document.len());
+    assert_eq!(Some(&Bson::Array(vec!["m6".into(), "din933".into()])), document.get("tags"));
+}
+
+#[test]
+fn test_find_with_sort() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_with_sort");
+    collection.drop().unwrap_or(());
+
+    for (group, position) in &[(1, 3), (2, 1), (1, 5), (2, 4), (1, 2)] {
+        collection.insert_one(&doc!{ "group": group, "position": position }, None).unwrap();
+    }
+
+    let mut options = CommandAndFindOptions::default();
+    options.sort = Some(Sort::new().desc("position").into());
+    let positions = collection.find(&doc!{}, Some(&options)).unwrap()
+        .map(|document| document.unwrap().get_i32("position").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![5, 4, 3, 2, 1], positions);
+
+    options.sort = Some(Sort::new().asc("group").desc("position").into());
+    let positions = collection.find(&doc!{}, Some(&options)).unwrap()
+        .map(|document| document.unwrap().get_i32("position").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![5, 3, 2, 4, 1], positions);
+}
+
+#[test]
+fn test_find_and_command_with_max_time() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_with_max_time");
+    collection.drop().unwrap_or(());
+
+    for i in 0..5 {
+        collection.insert_one(&doc!{ "key": i }, None).unwrap();
+    }
+
+    let mut options = CommandAndFindOptions::default();
+    options.max_time = Some(Duration::from_millis(10));
+
+    let query = doc!{ "$where": "sleep(100) || true" };
+    let error = match collection.find(&query, Some(&options)).unwrap().next().unwrap() {
+        Err(MongoError::Bsonc(error)) => error,
+        result => panic!("Unexpected result {:?}", result)
+    };
+    assert_eq!(MongoErrorCode::MaxTimeMsExpired, error.code());
+
+    let command = doc!{ "count": "find_with_max_time", "query": query };
+    let result = collection.command(command, Some(&options)).unwrap().next().unwrap();
+    assert!(result.is_err());
+
+    // Fast enough
+    options.max_time = Some(Duration::from_secs(10));
+    assert_eq!(5, collection.find(&doc!{}, Some(&options)).unwrap().count());
+}
+
+#[test]
+fn test_find_with_comment() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_driver_test");
+    let mut collection = database.get_collection("find_with_comment");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc!{ "key": 1 }, None).unwrap();
+
+    database.command_simple(doc!{ "profile": 2 }, None).unwrap();
+
+    let mut options = CommandAndFindOptions::default();
+    options.comment = Some("find_with_comment test".to_string());
+    assert_eq!(1, collection.find(&doc!{}, Some(&options)).unwrap().count());
+
+    database.command_simple(doc!{ "profile": 0 }, None).unwrap();
+
+    let profile = database.get_collection("system.profile");
+    let query   = doc!{ "ns": "rust_driver_test.find_with_comment", "command.comment": "find_with_comment test" };
+    assert!(profile.find(&query, None).unwrap().next().is_some());
+}
+
+#[test]
+fn test_find_invalid_utf8() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_invalid_utf8");
+    collection.drop().unwrap_or(());
+
+    let value = unsafe { String::from_utf8_unchecked(b"caf\xe9".to_vec()) };
+    collection.insert_one(&doc!{ "name": value }, None).unwrap();
+
+    // Lossy by default
+    let document = collection.find(&doc!{}, None).unwrap().next().unwrap().unwrap();
+    assert_eq!(Ok("caf\u{fffd}"), document.get_str("name"));
+
+    // Strict
+    let mut options = CommandAndFindOptions::default();
+    options.utf8_lossy = false;
+    let result = collection.find(&doc!{}, Some(&options)).unwrap().next().unwrap();
+    match result {
+        Err(MongoError::Decoder(_)) => (),
+        result => panic!("Unexpected result {:?}", result)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Item {
+    name:     String,
+    quantity: i32,
+    tags:     Vec<String>
+}
+
+#[test]
+fn test_find_typed() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "find_typed");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{ "name": "bolt", "quantity": 20, "tags": ["steel"] }, None).unwrap();
+    collection.insert_one(&doc!{ "name": "nut", "quantity": 5, "tags": [] }, None).unwrap();
+
+    let items = collection.find_typed::<Item>(&doc!{ "quantity": { "$gt": 10 } }, None).unwrap()
+        .collect::<mongo_driver::Result<Vec<_>>>().unwrap();
+    assert_eq!(vec![Item { name: "bolt".to_string(), quantity: 20, tags: vec!["steel".to_string()] }], items);
+
+    // A document that doesn't match the struct is a decoder error
+    collection.insert_one(&doc!{ "name": "washer" }, None).unwrap();
+    let result = collection.find_typed::<Item>(&doc!{ "name": "washer" }, None).unwrap().next().unwrap();
+    match result {
+        Err(MongoError::Decoder(_)) => (),
+        other => panic!("Unexpected result: {:?}", other)
+    }
+}
+
+#[test]
+fn test_insert_typed() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "insert_typed");
+    collection.drop().unwrap_or(());
+
+    let item = Item { name: "bolt".to_string(), quantity: 20, tags: vec!["steel".to_string()] };
+    collection.insert_typed(&item, None).unwrap();
+
+    let document = collection.find(&doc!{}, None).unwrap().next().unwrap().unwrap();
+    assert!(document.get_object_id("_id").is_ok());
+    assert_eq!(Ok("bolt"), document.get_str("name"));
+    assert_eq!(Ok(20), document.get_i32("quantity"));
+    assert_eq!(Some(&Bson::Array(vec![Bson::String("steel".to_string())])), document.get("tags"));
+
+    // A value that doesn't serialize to a document is an encoder error
+    match collection.insert_typed(&"not a document", None) {
+        Err(MongoError::Encoder(_)) => (),
+        other => panic!("Unexpected result: {:?}", other)
+    }
+}
+
+#[test]
+fn test_insert_one() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "insert_one");
+    collection.drop().unwrap_or(());
+
+    let document = doc!{"_id": 1, "key": 1};
+    let result   = collection.insert_one(&document, None).unwrap();
+    assert_eq!(InsertOneResult { inserted_id: Bson::Int32(1) }, result);
+
+    let result   = collection.insert_one(&doc!{"key": 2}, None).unwrap();
+    let inserted = collection.find(&doc!{"key": 2}, None).unwrap().next().unwrap().unwrap();
+    assert_eq!(Some(&result.inserted_id), inserted.get("_id"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_insert() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "deprecated_insert");
+    collection.drop().unwrap_or(());
+
+    collection.insert(&doc!{"key": 1}, None).unwrap();
+    assert_eq!(1, collection.count_documents(&doc!{"key": 1}, None).unwrap());
+}
+
+#[test]
+fn test_insert_returning_id() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "insert_returning_id");
+    collection.drop().unwrap_or(());
+
+    // An id is generated when the document has none
+    let id = match collection.insert_returning_id(&doc!{"key": 1}, None).unwrap() {
+        Bson::ObjectId(id) => id,
+        other => panic!("Unexpected id: {:?}", other)
+    };
+    let document = collection.find(&doc!{"key": 1}, None).unwrap().next().unwrap().unwrap();
+    assert_eq!(Ok(id), document.get_object_id("_id"));
+
+    // An existing id is returned as is
+    let id = collection.insert_returning_id(&doc!{"_id": "custom", "key": 2}, None).unwrap();
+    assert_eq!(Bson::String("custom".to_string()), id);
+    assert_eq!(2, collection.count_documents(&doc!{}, None).unwrap());
+}
+
+#[test]
+fn test_find_near() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_driver_test");
+    let mut collection = database.get_collection("find_near");
+    collection.drop().unwrap_or(());
+
+    let store = |name: &str, longitude: f64, latitude: f64| doc! {
+        "name": name,
+        "location": {"type": "Point", "coordinates": [longitude, latitude]}
+    };
+    collection.insert_one(&store("Amsterdam", 4.8897, 52.3740), None).unwrap();
+    collection.insert_one(&store("Utrecht", 5.1214, 52.0907), None).unwrap();
+    collection.insert_one(&store("Berlin", 13.4050, 52.5200), None).unwrap();
+
+    // Querying without a geospatial index fails
+    let result = collection.find_near("location", 4.9, 52.37, None, None).unwrap().next().unwrap();
+    assert!(result.is_err());
+
+    database.command_simple(doc!{
+        "createIndexes": "find_near",
+        "indexes": [{"key": {"location": "2dsphere"}, "name": "location_2dsphere"}]
+    }, None).unwrap();
+
+    // Nearest first
+    let documents = collection.find_near("location", 4.9, 52.37, None, None).unwrap()
+        .collect::<mongo_driver::Result<Vec<_>>>().unwrap();
+    assert_eq!(3, documents.len());
+    assert_eq!(Ok("Amsterdam"), documents[0].get_str("name"));
+    assert_eq!(Ok("Berlin"), documents[2].get_str("name"));
+
+    // Within 50km
+    let documents = collection.find_near("location", 4.9, 52.37, Some(50_000.0), None).unwrap()
+        .collect::<mongo_driver::Result<Vec<_>>>().unwrap();
+    assert_eq!(2, documents.len());
+
+    // Swapped coordinates are out of range
+    assert!(collection.find_near("location", 52.37, 94.9, None, None).is_err());
+}
+
+#[test]
+fn test_read_and_write_concern() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "collection_concerns");
+    collection.drop().unwrap_or(());
+
+    collection.set_write_concern(&WriteConcern::new(WriteConcernLevel::Majority));
+    collection.set_read_concern(&ReadConcern::new(ReadConcernLevel::Majority));
+    collection.set_read_prefs(&ReadPrefs::new(&ReadMode::PrimaryPreferred));
+    assert_eq!(WriteConcernLevel::Majority, collection.get_write_concern().level());
+    assert_eq!(Some(ReadConcernLevel::Majority), collection.get_read_concern().level());
+    assert!(collection.get_read_prefs().is_valid());
+
+    collection.insert_one(&doc!{ "key": 1 }, None).unwrap();
+    let documents = collection.find(&doc!{}, None).unwrap()
+        .collect::<mongo_driver::Result<Vec<_>>>().unwrap();
+    assert_eq!(1, documents.len());
+}
+
+#[test]
+fn test_rename() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_driver_test");
+    database.get_collection("renamed").drop().unwrap_or(());
+    let mut collection = database.get_collection("to_rename");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"key": 1}, None).unwrap();
+    collection.rename("rust_driver_test", "renamed", false).unwrap();
+
+    assert_eq!("renamed", collection.get_name().to_mut());
+    assert!(!database.has_collection("to_rename").unwrap());
+    assert!(database.has_collection("renamed").unwrap());
+    assert_eq!(1, collection.count_documents(&doc!{}, None).unwrap());
+}
+
+#[test]
+fn test_rename_existing_target() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut target = client.get_collection("rust_driver_test", "rename_target");
+    target.drop().unwrap_or(());
+    target.insert_one(&doc!{"key": 1}, None).unwrap();
+    let mut collection = client.get_collection("rust_driver_test", "rename_source");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc!{"key": 2}, None).unwrap();
+
+    assert!(collection.rename("rust_driver_test", "rename_target", false).is_err());
+    collection.rename("rust_driver_test", "rename_target", true).unwrap();
+    assert_eq!(1, collection.count_documents(&doc!{"key": 2}, None).unwrap());
+}
+
+#[test]
+fn test_insert_many() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "insert_many");
+    collection.drop().unwrap_or(());
+
+    let documents: Vec<_> = (0..1000).map(|i| doc!{"key": i}).collect();
+    let reply = collection.insert_many(&documents, None).unwrap();
+
+    assert_eq!(Ok(1000), reply.get_i32("nInserted"));
+    assert_eq!(1000, collection.count_documents(&doc!{}, None).unwrap());
+}
+
+#[test]
+fn test_insert_many_ordered_failure() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "insert_many_ordered_failure");
+    collection.drop().unwrap_or(());
+
+    let documents = vec![doc!{"_id": 1}, doc!{"_id": 1}, doc!{"_id": 2}];
+    match collection.insert_many(&documents, None) {
+        Err(MongoError::Reply(error)) => {
+            assert_eq!(Ok(1), error.reply.get_i32("nInserted"));
+            assert_eq!(1, error.reply.get_array("writeErrors").unwrap().len());
+        },
+        result => panic!("Unexpected result {:?}", result)
+    }
+    assert_eq!(1, collection.count_documents(&doc!{}, None).unwrap());
+
+    collection.drop().unwrap_or(());
+    let mut options = InsertManyOptions::default();
+    options.ordered = false;
+    match collection.insert_many(&documents, Some(&options)) {
+        Err(MongoError::Reply(error)) => {
+            assert_eq!(Ok(2), error.reply.get_i32("nInserted"));
+            assert_eq!(1, error.reply.get_array("writeErrors").unwrap().len());
+        },
+        result => panic!("Unexpected result {:?}", result)
+    }
+    assert_eq!(2, collection.count_documents(&doc!{}, None).unwrap());
+}
+
+#[test]
+fn test_insert_many_empty() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let collection = client.get_collection("rust_driver_test", "insert_many_empty");
+
+    match collection.insert_many(&[], None) {
+        Err(MongoError::InvalidParams(_)) => (),
+        result => panic!("Unexpected result {:?}", result)
+    }
+}
+
+#[test]
+fn test_update_one_and_many() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "update_one_and_many");
+    collection.drop().unwrap_or(());
+
+    for key in &[1, 1, 1, 2] {
+        collection.insert_one(&doc!{"key": key, "value": 0}, None).unwrap();
+    }
+
+    let result = collection.update_one(&doc!{"key": 1}, &doc!{"$set": {"value": 1}}, None).unwrap();
+    assert_eq!(UpdateResult { matched_count: 1, modified_count: 1, upserted_id: None }, result);
+
+    let result = collection.update_many(&doc!{"key": 1}, &doc!{"$set": {"value": 1}}, None).unwrap();
+    assert_eq!(3, result.matched_count);
+    assert_eq!(2, result.modified_count);
+    assert_eq!(3, collection.count_documents(&doc!{"value": 1}, None).unwrap());
+
+    let mut options = UpdateOptions::default();
+    options.update_flags.add(flags::UpdateFlag::Upsert);
+    let result = collection.update_many(&doc!{"key": 3}, &doc!{"$set": {"value": 1}}, Some(&options)).unwrap();
+    assert_eq!(0, result.matched_count);
+    assert!(result.upserted_id.is_some());
+}
+
+#[test]
+fn test_update_invalid_replacement() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "update_invalid_replacement");
+    collection.drop().unwrap_or(());
+
+    for _ in 0..2 {
+        collection.insert_one(&doc!{"key": 1, "value": 0}, None).unwrap();
+    }
+
+    // A replacement only replaces one document, so multi update is not allowed
+    let mut options = UpdateOptions::default();
+    options.update_flags.add(flags::UpdateFlag::MultiUpdate);
+    let result = collection.update(&doc!{"key": 1}, &doc!{"key": 1, "value": 1}, Some(&options));
+    assert!(format!("{:?}", result.err().unwrap()).contains("InvalidParamsError"));
+    assert_eq!(0, collection.count_documents(&doc!{"value": 1}, None).unwrap());
+
+    // An empty update document would replace the document with an empty one
+    let result = collection.update(&doc!{"key": 1}, &doc!{}, None);
+    assert!(format!("{:?}", result.err().unwrap()).contains("InvalidParamsError"));
+    assert_eq!(2, collection.count_documents(&doc!{"key": 1}, None).unwrap());
+}
+
+#[test]
+fn test_replace_one() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "replace_one");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"key": 1, "value": 0}, None).unwrap();
+
+    let result = collection.replace_one(&doc!{"key": 1}, &doc!{"key": 1, "other": 1}, None).unwrap();
+    assert_eq!(UpdateResult { matched_count: 1, modified_count: 1, upserted_id: None }, result);
+    let document = collection.find(&doc!{"key": 1}, None).unwrap().next().unwrap().unwrap();
+    assert!(!document.contains_key("value"));
+
+    // Update operators are not allowed in a replacement
+    assert!(collection.replace_one(&doc!{"key": 1}, &doc!{"$set": {"value": 1}}, None).is_err());
+
+    let mut options = ReplaceOptions::default();
+    options.upsert = true;
+    let result = collection.replace_one(&doc!{"key": 2}, &doc!{"key": 2}, Some(&options)).unwrap();
+    assert_eq!(0, result.matched_count);
+    assert!(result.upserted_id.is_some());
+}
+
 #[test]
 fn test_insert_failure() {
     let uri        = Uri::new("mongodb://localhost:27018/?serverSelectionTimeoutMS=1").unwrap(); // There should be no mongo server here
@@ -239,7 +1212,24 @@ fn test_insert_failure() {
     let collection = client.get_collection("rust_driver_test", "items");
     let document   = doc! {};
 
-    let result = collection.insert(&document, None);
+    let result = collection.insert_one(&document, None);
     assert!(result.is_err());
     assert!(format!("{:?}", result.err().unwrap()).contains("No suitable servers found"));
 }
+
+#[test]
+fn test_insert_failure_with_server_selection_timeout() {
+    let mut uri = Uri::new("mongodb://localhost:27018/").unwrap(); // There should be no mongo server here
+    uri.set_server_selection_timeout(Duration::from_millis(1)).unwrap();
+    assert_eq!(Some(Duration::from_millis(1)), uri.get_server_selection_timeout());
+    assert_eq!(Some(1), uri.get_option_i32("serverSelectionTimeoutMS"));
+
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let collection = client.get_collection("rust_driver_test", "items");
+
+    let start  = Instant::now();
+    let result = collection.insert_one(&doc!{}, None);
+    assert!(result.is_err());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
@@ -1,5 +1,7 @@
 extern crate bson;
 extern crate mongo_driver;
+#[macro_use]
+extern crate serde_derive;
 
 mod helpers;
 
@@ -10,7 +12,10 @@ use std::time::Duration;
 use bson::doc;
 
 use mongo_driver::client::{ClientPool,Uri};
-use mongo_driver::Result;
+use mongo_driver::collection::{InsertOptions,TailOptions};
+use mongo_driver::read_prefs::{ReadMode,ReadPrefs};
+use mongo_driver::write_concern::{WriteConcern,WriteConcernLevel};
+use mongo_driver::{CommandAndFindOptions,Result};
 
 #[test]
 fn test_cursor() {
@@ -23,7 +28,7 @@ fn test_cursor() {
 
     collection.drop().unwrap_or(());
     for _ in 0..10 {
-        collection.insert(&document, None).expect("Could not insert");
+        collection.insert_one(&document, None).expect("Could not insert");
     }
 
     let query  = doc! {};
@@ -35,6 +40,127 @@ fn test_cursor() {
     assert_eq!(10, documents.len());
 }
 
+#[test]
+fn test_cursor_try_collect() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "cursor_try_collect");
+    collection.drop().unwrap_or(());
+
+    for i in &[1, 2, 3, 0, 4] {
+        collection.insert_one(&doc! { "divisor": i }, None).unwrap();
+    }
+
+    let documents = collection.find(&doc! {}, None).unwrap().try_collect().unwrap();
+    assert_eq!(5, documents.len());
+
+    // Dividing by zero fails on the server when the fourth document is reached, which
+    // is in the second batch.
+    let mut options = CommandAndFindOptions::default();
+    options.batch_size = 2;
+    let query = doc! { "$expr": { "$gt": [{ "$divide": [1, "$divisor"] }, 0] } };
+    let mut cursor = collection.find(&query, Some(&options)).unwrap();
+    assert!(cursor.next().unwrap().is_ok());
+    let cursor = collection.find(&query, Some(&options)).unwrap();
+    assert!(cursor.try_collect().is_err());
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Position {
+    key:   String,
+    index: i32
+}
+
+#[test]
+fn test_cursor_deserialize() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "cursor_deserialize");
+    collection.drop().unwrap_or(());
+
+    for i in 0..3 {
+        collection.insert_one(&doc! { "key": "value", "index": i }, None).unwrap();
+    }
+
+    let mut options = CommandAndFindOptions::default();
+    options.sort = Some(doc! { "index": 1 });
+    let positions = collection.find(&doc! {}, Some(&options)).unwrap()
+        .deserialize::<Position>()
+        .collect::<Result<Vec<Position>>>()
+        .unwrap();
+
+    let expected = (0..3).map(|i| Position { key: "value".to_string(), index: i }).collect::<Vec<_>>();
+    assert_eq!(expected, positions);
+}
+
+#[test]
+fn test_clone_cursor() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "clone_cursor");
+    collection.drop().unwrap_or(());
+
+    let documents: Vec<_> = (0..10).map(|i| doc! { "key": i }).collect();
+    collection.insert_many(&documents, None).unwrap();
+
+    let mut options = CommandAndFindOptions::with_fields(doc! { "key": true });
+    options.batch_size = 3;
+    let mut cursor = collection.find(&doc! {}, Some(&options)).unwrap();
+    assert_eq!(10, cursor.by_ref().count());
+
+    let cloned = cursor.clone_cursor();
+    drop(cursor);
+
+    let keys: Vec<i32> = cloned
+        .map(|document| document.unwrap().get_i32("key").unwrap())
+        .collect();
+    assert_eq!((0..10).collect::<Vec<i32>>(), keys);
+}
+
+#[test]
+fn test_cursor_batch_size() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "cursor_batch_size");
+    collection.drop().unwrap_or(());
+
+    let documents: Vec<_> = (0..500).map(|i| doc! { "key": i }).collect();
+    collection.insert_many(&documents, None).unwrap();
+
+    let mut cursor = collection.find(&doc! {}, None).unwrap();
+    cursor.set_batch_size(7);
+    assert_eq!(7, cursor.batch_size());
+
+    assert_eq!(500, cursor.filter(|document| document.is_ok()).count());
+}
+
+#[test]
+fn test_cursor_id_and_server_id() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "cursor_id");
+    collection.drop().unwrap_or(());
+
+    let documents: Vec<_> = (0..10).map(|i| doc! { "key": i }).collect();
+    collection.insert_many(&documents, None).unwrap();
+
+    let mut cursor = collection.find(&doc! {}, None).unwrap();
+    cursor.set_batch_size(2);
+    assert_eq!(0, cursor.server_id());
+
+    assert!(cursor.next().unwrap().is_ok());
+    assert!(cursor.cursor_id() != 0);
+    assert!(cursor.server_id() > 0);
+
+    assert_eq!(9, cursor.by_ref().count());
+    assert_eq!(0, cursor.cursor_id());
+}
+
 #[test]
 fn test_tailing_cursor() {
     // See: http://mongoc.org/libmongoc/current/cursors.html#tailable
@@ -60,7 +186,7 @@ fn test_tailing_cursor() {
 
     let document = doc! { "key_1": "Value 1" };
     // Insert a first document into the collection
-    capped_collection.insert(&document, None).unwrap();
+    capped_collection.insert_one(&document, None).unwrap();
 
     // Start a tailing iterator in a thread
     let cloned_pool = pool.clone();
@@ -84,7 +210,7 @@ fn test_tailing_cursor() {
 
     // Insert some more documents into the collection
     for _ in 0..25 {
-        capped_collection.insert(&document, None).unwrap();
+        capped_collection.insert_one(&document, None).unwrap();
     }
 
     // See if they appeared while iterating the cursor
@@ -93,6 +219,55 @@ fn test_tailing_cursor() {
     assert_eq!(25, guard.join().expect("Thread failed"));
 }
 
+#[test]
+fn test_tailing_cursor_max_await_time() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = Arc::new(ClientPool::new(uri, None));
+    let client   = pool.pop();
+    let database = client.get_database("rust_test");
+    database.get_collection("capped_max_await_time").drop().unwrap_or(());
+
+    let options = doc! {
+        "capped": true,
+        "size": 100000
+    };
+    let capped_collection = database.create_collection("capped_max_await_time", Some(&options)).unwrap();
+
+    let document = doc! { "key_1": "Value 1" };
+    capped_collection.insert_one(&document, None).unwrap();
+
+    let cloned_pool = pool.clone();
+    let guard = thread::spawn(move || {
+        let client     = cloned_pool.pop();
+        let collection = client.get_collection("rust_test", "capped_max_await_time");
+        let mut tail_options = TailOptions::default();
+        tail_options.max_await_time = Some(Duration::from_millis(100));
+        let cursor = collection.tail(doc!{}, None, Some(tail_options));
+        cursor.into_iter().take(10).map(|result| result.expect("Error tailing")).count()
+    });
+
+    thread::sleep(Duration::from_secs(1));
+
+    for _ in 0..9 {
+        capped_collection.insert_one(&document, None).unwrap();
+    }
+
+    assert_eq!(10, guard.join().expect("Thread failed"));
+}
+
+#[test]
+fn test_cursor_max_await_time() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let collection = client.get_collection("rust_driver_test", "cursor_max_await_time");
+
+    let mut cursor = collection.find(&doc! {}, None).unwrap();
+    assert_eq!(Duration::from_millis(0), cursor.max_await_time());
+    cursor.set_max_await_time(Duration::from_millis(250));
+    assert_eq!(Duration::from_millis(250), cursor.max_await_time());
+}
+
 #[test]
 fn test_batch_cursor() {
     let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
@@ -137,3 +312,78 @@ fn test_batch_cursor() {
 
     collection.drop().unwrap();
 }
+
+#[test]
+fn test_batch_cursor_with_read_prefs() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_test");
+    let mut collection = database.get_collection("test_batch_cursor_read_prefs");
+    collection.drop().unwrap_or(());
+
+    let mut insert_options = InsertOptions::default();
+    insert_options.write_concern = WriteConcern::new(WriteConcernLevel::Majority);
+    for i in 0..25 {
+        collection.insert_one(&doc!{"key": i}, Some(&insert_options)).unwrap();
+    }
+
+    // Read from a secondary if there is one, the getMores have to go to the same node
+    let mut options = CommandAndFindOptions::default();
+    options.read_prefs = Some(ReadPrefs::new(&ReadMode::SecondaryPreferred));
+    let cursor = database.command_batch(
+        doc!{"find": "test_batch_cursor_read_prefs", "batchSize": 10},
+        Some(&options)
+    ).unwrap();
+
+    let documents = cursor.collect::<Result<Vec<_>>>().expect("Could not iterate batches");
+    assert_eq!(25, documents.len());
+}
+
+#[test]
+fn test_batch_cursor_with_batch_size() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_test");
+    let mut collection = database.get_collection("test_batch_cursor_batch_size");
+    collection.drop().unwrap_or(());
+
+    let documents: Vec<_> = (0..10000).map(|i| doc!{"key": i}).collect();
+    collection.insert_many(&documents, None).unwrap();
+
+    let mut options = CommandAndFindOptions::default();
+    options.batch_size = 1000;
+    let cursor = database.command_batch(
+        doc!{"find": "test_batch_cursor_batch_size", "batchSize": 1000},
+        Some(&options)
+    ).unwrap();
+
+    let documents = cursor.collect::<Result<Vec<_>>>().expect("Could not iterate batches");
+    assert_eq!(10000, documents.len());
+}
+
+#[test]
+fn test_batch_cursor_get_more_error() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_test");
+    let mut collection = database.get_collection("test_batch_cursor_get_more_error");
+    collection.drop().unwrap_or(());
+
+    let documents: Vec<_> = (0..10).map(|i| doc!{"key": i}).collect();
+    collection.insert_many(&documents, None).unwrap();
+
+    let mut cursor = database.command_batch(
+        doc!{"find": "test_batch_cursor_get_more_error", "batchSize": 2},
+        None
+    ).unwrap();
+    assert!(cursor.next().unwrap().is_ok());
+    assert!(cursor.next().unwrap().is_ok());
+
+    // Dropping the collection kills the cursor on the server, so the getMore fails
+    collection.drop().unwrap();
+    assert!(cursor.next().unwrap().is_err());
+    assert!(cursor.next().is_none());
+}
@@ -0,0 +1,71 @@
+extern crate bson;
+extern crate mongo_driver;
+
+mod helpers;
+
+use std::alloc::{GlobalAlloc,Layout,System};
+use std::sync::atomic::{AtomicUsize,Ordering};
+
+use bson::{doc,Document};
+
+use mongo_driver::client::{ClientPool,Uri};
+
+// Counts the bytes allocated by Rust code in this test binary, so the
+// peak memory usage while iterating a cursor can be checked.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK:      AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_bounded_iter_memory() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "cursor_bounded_iter");
+    collection.drop().unwrap_or(());
+
+    let padding = "x".repeat(100);
+    for batch in 0..50 {
+        let documents: Vec<Document> = (0..1000).map(|i| {
+            doc! { "number": batch * 1000 + i, "padding": padding.clone() }
+        }).collect();
+        collection.insert_many(&documents, None).expect("Could not insert");
+    }
+
+    let cursor = collection.find(&doc!{}, None).unwrap().into_bounded_iter(100);
+    assert_eq!(100, cursor.batch_size());
+
+    let start = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(start, Ordering::SeqCst);
+
+    let mut count = 0;
+    for result in cursor {
+        result.unwrap();
+        count += 1;
+    }
+    assert_eq!(50_000, count);
+
+    // Collecting all documents would take tens of megabytes
+    let peak_increase = PEAK.load(Ordering::SeqCst) - start;
+    assert!(peak_increase < 1024 * 1024, "Peak allocation increased by {} bytes", peak_increase);
+}
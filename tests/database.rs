@@ -1,11 +1,19 @@
 extern crate bson;
 extern crate mongo_driver;
+#[macro_use]
+extern crate serde_derive;
 
 mod helpers;
 
+use std::env;
+
 use bson::doc;
 
+use mongo_driver::MongoError;
 use mongo_driver::client::{ClientPool,Uri};
+use mongo_driver::read_concern::{ReadConcern,ReadConcernLevel};
+use mongo_driver::read_prefs::{ReadMode,ReadPrefs};
+use mongo_driver::write_concern::{WriteConcern,WriteConcernLevel};
 
 #[test]
 fn test_command() {
@@ -62,6 +70,99 @@ fn test_create_collection() {
     assert_eq!("created_collection", collection.get_name().to_mut());
 }
 
+#[derive(Deserialize)]
+struct BuildInfo {
+    version: String
+}
+
+#[derive(Debug,Deserialize)]
+struct MissingField {
+    #[serde(rename = "notInTheReply")]
+    _not_in_the_reply: String
+}
+
+#[test]
+fn test_run_command_typed() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("admin");
+
+    let build_info: BuildInfo = database.run_command_typed(doc! { "buildInfo": 1 }, None).unwrap();
+    assert!(!build_info.version.is_empty());
+
+    match database.run_command_typed::<MissingField>(doc! { "buildInfo": 1 }, None) {
+        Err(MongoError::Decoder(_)) => (),
+        other => panic!("Unexpected result: {:?}", other)
+    }
+}
+
+#[test]
+fn test_drop() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut database = client.get_database("rust_test_drop");
+
+    database.get_collection("items").insert_one(&doc! { "key": 1 }, None).unwrap();
+    assert!(client.get_database_names(None).unwrap().contains(&"rust_test_drop".to_string()));
+
+    database.drop().unwrap();
+    assert!(!client.get_database_names(None).unwrap().contains(&"rust_test_drop".to_string()));
+
+    // Dropping it again succeeds
+    database.drop().unwrap();
+}
+
+#[test]
+fn test_add_and_remove_user() {
+    if env::var("MONGO_RUST_DRIVER_AUTH_TESTS") != Ok("true".to_string()) {
+        return
+    }
+
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_test_users");
+    database.remove_user("rust_test_user").unwrap_or(());
+
+    let roles = doc! { "0": { "role": "read", "db": "rust_test_users" } };
+    database.add_user("rust_test_user", "secret", &roles).unwrap();
+    assert!(database.add_user("rust_test_user", "secret", &roles).is_err());
+
+    let info = database.command_simple(doc! { "usersInfo": "rust_test_user" }, None).unwrap();
+    assert_eq!(1, info.get_array("users").unwrap().len());
+
+    database.remove_user("rust_test_user").unwrap();
+    let info = database.command_simple(doc! { "usersInfo": "rust_test_user" }, None).unwrap();
+    assert!(info.get_array("users").unwrap().is_empty());
+    assert!(database.remove_user("rust_test_user").is_err());
+}
+
+#[test]
+fn test_read_and_write_concern() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let mut database = client.get_database("rust_test");
+
+    assert_eq!(WriteConcernLevel::Default, database.get_write_concern().level());
+    assert!(database.get_read_concern().level().is_none());
+
+    database.set_write_concern(&WriteConcern::new(WriteConcernLevel::Majority));
+    database.set_read_concern(&ReadConcern::new(ReadConcernLevel::Local));
+    database.set_read_prefs(&ReadPrefs::new(&ReadMode::PrimaryPreferred));
+    assert_eq!(WriteConcernLevel::Majority, database.get_write_concern().level());
+    assert_eq!(Some(ReadConcernLevel::Local), database.get_read_concern().level());
+    assert!(database.get_read_prefs().is_valid());
+
+    // A collection from the database inherits the write concern
+    let mut collection = database.get_collection("database_concerns");
+    collection.drop().unwrap_or(());
+    collection.insert_one(&doc! { "key": 1 }, None).unwrap();
+    assert_eq!(1, collection.count_documents(&doc! {}, None).unwrap());
+}
+
 #[test]
 fn test_has_collection() {
     let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
@@ -81,3 +182,23 @@ fn test_has_collection() {
     assert_eq!(COLL_NAME, collection.get_name().to_mut());
     assert!(database.has_collection(COLL_NAME).unwrap());
 }
+
+#[test]
+fn test_get_collection_names() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_test_collection_names");
+
+    for name in &["names_1", "names_2"] {
+        database.get_collection(*name).drop().unwrap_or(());
+        database.create_collection(*name, None).unwrap();
+    }
+
+    let names = database.get_collection_names(None).unwrap();
+    assert!(names.contains(&"names_1".to_string()));
+    assert!(names.contains(&"names_2".to_string()));
+
+    let names = database.get_collection_names(Some(&doc! { "name": "names_1" })).unwrap();
+    assert_eq!(vec!["names_1".to_string()], names);
+}
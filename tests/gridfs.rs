@@ -0,0 +1,183 @@
+extern crate bson;
+extern crate mongo_driver;
+
+mod helpers;
+
+use std::io::{Cursor,Read,Seek,SeekFrom};
+
+use bson::{doc,Bson};
+use bson::document::ValueAccessError;
+
+use mongo_driver::MongoError;
+use mongo_driver::client::{ClientPool,Uri};
+
+#[test]
+fn test_upload_and_download() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let gridfs = client.get_gridfs("rust_driver_test", "fs").unwrap();
+
+    let data: Vec<u8> = (0..8192).map(|i| (i % 251) as u8).collect();
+    let id = gridfs.upload_from_stream("data.bin", &mut Cursor::new(&data)).unwrap();
+
+    let mut downloaded = Vec::new();
+    gridfs.download_to_stream(&id, &mut downloaded).unwrap();
+    assert_eq!(data, downloaded);
+}
+
+#[test]
+fn test_download_stream_seek() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let gridfs = client.get_gridfs("rust_driver_test", "fs").unwrap();
+
+    // Three chunks of the default chunk size of 255 KiB
+    let chunk_size = 255 * 1024;
+    let data: Vec<u8> = (0..(chunk_size * 2 + 1000)).map(|i| (i % 251) as u8).collect();
+    let id = gridfs.upload_from_stream("seek.bin", &mut Cursor::new(&data)).unwrap();
+
+    let mut stream = gridfs.open_download_stream(&id).unwrap();
+    assert_eq!(data.len() as u64, stream.len());
+
+    // Read from the start
+    let mut buffer = vec![0u8; 100];
+    stream.read_exact(&mut buffer).unwrap();
+    assert_eq!(&data[..100], &buffer[..]);
+
+    // Read across a chunk boundary
+    assert_eq!(chunk_size as u64 - 50, stream.seek(SeekFrom::Start(chunk_size as u64 - 50)).unwrap());
+    stream.read_exact(&mut buffer).unwrap();
+    assert_eq!(&data[chunk_size - 50..chunk_size + 50], &buffer[..]);
+
+    // Seek backwards and relative to the end
+    stream.seek(SeekFrom::Current(-200)).unwrap();
+    stream.read_exact(&mut buffer).unwrap();
+    assert_eq!(&data[chunk_size - 150..chunk_size - 50], &buffer[..]);
+
+    stream.seek(SeekFrom::End(-10)).unwrap();
+    let mut rest = Vec::new();
+    stream.read_to_end(&mut rest).unwrap();
+    assert_eq!(&data[data.len() - 10..], &rest[..]);
+
+    // Past the end there is nothing to read, before the start is an error
+    assert_eq!(data.len() as u64 + 10, stream.seek(SeekFrom::End(10)).unwrap());
+    assert_eq!(0, stream.read(&mut buffer).unwrap());
+    assert!(stream.seek(SeekFrom::Current(-(data.len() as i64) - 20)).is_err());
+
+    // Read everything from the start again
+    stream.seek(SeekFrom::Start(0)).unwrap();
+    let mut all = Vec::new();
+    stream.read_to_end(&mut all).unwrap();
+    assert_eq!(data, all);
+}
+
+#[test]
+fn test_open_download_stream_missing_file() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let gridfs = client.get_gridfs("rust_driver_test", "fs").unwrap();
+
+    assert!(gridfs.open_download_stream(&bson::oid::ObjectId::new()).is_err());
+}
+
+#[test]
+fn test_download_missing_file() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let gridfs = client.get_gridfs("rust_driver_test", "fs").unwrap();
+
+    let mut downloaded = Vec::new();
+    assert!(gridfs.download_to_stream(&bson::oid::ObjectId::new(), &mut downloaded).is_err());
+}
+
+#[test]
+fn test_find_and_delete() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let gridfs = client.get_gridfs("rust_driver_test", "find_and_delete").unwrap();
+
+    let mut files  = client.get_collection("rust_driver_test", "find_and_delete.files");
+    let mut chunks = client.get_collection("rust_driver_test", "find_and_delete.chunks");
+    files.drop().unwrap_or(());
+    chunks.drop().unwrap_or(());
+
+    let first  = gridfs.upload_from_stream("first.txt", &mut Cursor::new(b"first")).unwrap();
+    let second = gridfs.upload_from_stream("second.txt", &mut Cursor::new(b"second")).unwrap();
+
+    let found: Vec<_> = gridfs.find(&doc! {}, None).unwrap().map(|file| file.unwrap()).collect();
+    assert_eq!(2, found.len());
+    assert_eq!(5, found.iter().find(|file| file.get_str("filename").unwrap() == "first.txt").unwrap().get_i64("length").unwrap());
+
+    gridfs.delete(&Bson::ObjectId(first)).unwrap();
+    match gridfs.delete(&Bson::ObjectId(first)) {
+        Err(MongoError::ValueAccessError(ValueAccessError::NotPresent)) => (),
+        result => panic!("Unexpected result {:?}", result)
+    }
+
+    let remaining: Vec<_> = gridfs.find(&doc! {}, None).unwrap().map(|file| file.unwrap()).collect();
+    assert_eq!(1, remaining.len());
+    assert_eq!(second, remaining[0].get_object_id("_id").unwrap());
+    assert_eq!(0, chunks.count(&doc! { "files_id": first }, None).unwrap());
+}
+
+#[test]
+fn test_find_by_metadata_and_upload_date() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let gridfs = client.get_gridfs("rust_driver_test", "find_by_metadata").unwrap();
+
+    let mut files  = client.get_collection("rust_driver_test", "find_by_metadata.files");
+    let mut chunks = client.get_collection("rust_driver_test", "find_by_metadata.chunks");
+    files.drop().unwrap_or(());
+    chunks.drop().unwrap_or(());
+
+    let old = gridfs.upload_from_stream("old.txt", &mut Cursor::new(b"old")).unwrap();
+    let new = gridfs.upload_from_stream("new.txt", &mut Cursor::new(b"new")).unwrap();
+    let month_ago = bson::DateTime::from_millis(bson::DateTime::now().timestamp_millis() - 30 * 24 * 3600 * 1000);
+    files.update(
+        &doc! { "_id": old },
+        &doc! { "$set": { "uploadDate": month_ago, "metadata": { "owner": "cleanup" } } },
+        None
+    ).unwrap();
+
+    let found: Vec<_> = gridfs.find(&doc! { "metadata.owner": "cleanup" }, None).unwrap().map(|file| file.unwrap()).collect();
+    assert_eq!(1, found.len());
+    assert_eq!(old, found[0].get_object_id("_id").unwrap());
+
+    let week_ago = bson::DateTime::from_millis(bson::DateTime::now().timestamp_millis() - 7 * 24 * 3600 * 1000);
+    let expired: Vec<_> = gridfs.find(&doc! { "uploadDate": { "$lt": week_ago } }, None).unwrap().map(|file| file.unwrap()).collect();
+    assert_eq!(1, expired.len());
+    assert_eq!(old, expired[0].get_object_id("_id").unwrap());
+
+    let opts = doc! { "sort": { "uploadDate": -1 }, "limit": 1 };
+    let newest: Vec<_> = gridfs.find(&doc! {}, Some(&opts)).unwrap().map(|file| file.unwrap()).collect();
+    assert_eq!(1, newest.len());
+    assert_eq!(new, newest[0].get_object_id("_id").unwrap());
+}
+
+#[test]
+fn test_delete_file_with_custom_id() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+    let gridfs = client.get_gridfs("rust_driver_test", "delete_custom_id").unwrap();
+
+    let mut files = client.get_collection("rust_driver_test", "delete_custom_id.files");
+    files.drop().unwrap_or(());
+    files.insert_one(&doc! {
+        "_id":        "custom",
+        "length":     0i64,
+        "chunkSize":  255 * 1024,
+        "uploadDate": bson::DateTime::now(),
+        "filename":   "empty.txt"
+    }, None).unwrap();
+
+    gridfs.delete(&Bson::String("custom".to_string())).unwrap();
+    assert_eq!(0, files.count(&doc! {}, None).unwrap());
+}
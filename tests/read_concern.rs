@@ -0,0 +1,76 @@
+extern crate bson;
+extern crate mongo_driver;
+
+mod helpers;
+
+use bson::doc;
+
+use mongo_driver::CommandAndFindOptions;
+use mongo_driver::client::{ClientPool,Uri};
+use mongo_driver::collection::AggregateOptions;
+use mongo_driver::read_concern::{ReadConcern,ReadConcernLevel};
+
+#[test]
+fn test_default_read_concern() {
+    let read_concern = ReadConcern::default();
+    assert!(!read_concern.inner().is_null());
+    assert_eq!(None, read_concern.level());
+}
+
+#[test]
+fn test_read_concern_levels() {
+    for level in vec![
+        ReadConcernLevel::Local,
+        ReadConcernLevel::Majority,
+        ReadConcernLevel::Linearizable,
+        ReadConcernLevel::Available,
+        ReadConcernLevel::Snapshot
+    ] {
+        let read_concern = ReadConcern::new(level);
+        assert!(read_concern.level().is_some());
+    }
+    assert_eq!(
+        Some(ReadConcernLevel::Majority),
+        ReadConcern::new(ReadConcernLevel::Majority).level()
+    );
+}
+
+#[test]
+fn test_find_and_aggregate_with_read_concern() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "read_concern");
+    collection.drop().unwrap_or(());
+
+    collection.insert_one(&doc!{"key": 1}, None).expect("Could not insert");
+
+    let mut find_options = CommandAndFindOptions::default();
+    find_options.read_concern = Some(ReadConcern::new(ReadConcernLevel::Local));
+    assert_eq!(1, collection.find(&doc!{}, Some(&find_options)).unwrap().count());
+
+    let mut aggregate_options = AggregateOptions::default();
+    aggregate_options.read_concern = Some(ReadConcern::new(ReadConcernLevel::Local));
+    let pipeline = doc!{
+        "pipeline": [
+            { "$match": { "key": 1 } }
+        ]
+    };
+    assert_eq!(1, collection.aggregate(&pipeline, Some(&aggregate_options)).unwrap().count());
+}
+
+#[test]
+fn test_command_with_read_concern_is_rejected() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let database   = client.get_database("rust_driver_test");
+    let collection = database.get_collection("read_concern_command");
+
+    let mut options = CommandAndFindOptions::default();
+    options.read_concern = Some(ReadConcern::new(ReadConcernLevel::Majority));
+
+    assert!(collection.command(doc!{"count": "read_concern_command"}, Some(&options)).is_err());
+    assert!(database.command(doc!{"ping": 1}, Some(&options)).is_err());
+    assert!(database.command_batch(doc!{"find": "read_concern_command"}, Some(&options)).is_err());
+}
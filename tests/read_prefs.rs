@@ -1,9 +1,58 @@
+extern crate bson;
 extern crate mongo_driver;
 
-use mongo_driver::read_prefs::ReadPrefs;
+use std::time::Duration;
+
+use bson::doc;
+
+use mongo_driver::read_prefs::{ReadMode,ReadPrefs};
 
 #[test]
 fn test_read_prefs() {
     let read_prefs = ReadPrefs::default();
     assert!(!read_prefs.inner().is_null());
 }
+
+#[test]
+fn test_max_staleness() {
+    let mut read_prefs = ReadPrefs::new(&ReadMode::Secondary);
+    assert_eq!(None, read_prefs.max_staleness());
+
+    read_prefs.set_max_staleness(Duration::from_secs(120)).unwrap();
+    assert_eq!(Some(Duration::from_secs(120)), read_prefs.max_staleness());
+}
+
+#[test]
+fn test_max_staleness_too_small() {
+    let mut read_prefs = ReadPrefs::new(&ReadMode::Secondary);
+    assert!(read_prefs.set_max_staleness(Duration::from_secs(89)).is_err());
+    assert_eq!(None, read_prefs.max_staleness());
+}
+
+#[test]
+fn test_hedge() {
+    let mut read_prefs = ReadPrefs::new(&ReadMode::SecondaryPreferred);
+    read_prefs.set_hedge(&doc! { "enabled": true }).unwrap();
+    assert!(read_prefs.is_valid());
+}
+
+#[test]
+fn test_hedge_primary() {
+    let mut read_prefs = ReadPrefs::default();
+    assert!(read_prefs.set_hedge(&doc! { "enabled": true }).is_err());
+    assert!(read_prefs.is_valid());
+}
+
+#[test]
+fn test_clone() {
+    let read_prefs = ReadPrefs::new(&ReadMode::SecondaryPreferred);
+    let cloned     = read_prefs.clone();
+    assert_ne!(read_prefs.inner(), cloned.inner());
+    assert_eq!(ReadMode::SecondaryPreferred, read_prefs.mode());
+    assert!(read_prefs.is_valid());
+
+    drop(read_prefs);
+    assert_eq!(ReadMode::SecondaryPreferred, cloned.mode());
+    assert!(cloned.is_valid());
+    assert_eq!("ReadPrefs { mode: SecondaryPreferred, tags: [] }", format!("{:?}", cloned));
+}
@@ -0,0 +1,222 @@
+extern crate bson;
+extern crate mongo_driver;
+
+mod helpers;
+
+use bson::doc;
+
+use mongo_driver::CommandAndFindOptions;
+use mongo_driver::client::{ClientPool,Uri};
+use mongo_driver::collection::{AggregateOptions,InsertOptions};
+use mongo_driver::session::{SessionOptions,TransactionOptions};
+use mongo_driver::write_concern::{WriteConcern,WriteConcernLevel};
+
+#[test]
+fn test_start_session() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    let session = client.start_session(None).unwrap();
+    assert!(session.causal_consistency());
+    assert!(session.lsid().unwrap().contains_key("id"));
+    assert!(session.operation_time().is_none());
+
+    let mut options = SessionOptions::default();
+    options.causal_consistency = false;
+    let session = client.start_session(Some(&options)).unwrap();
+    assert!(!session.causal_consistency());
+}
+
+#[test]
+fn test_operation_and_cluster_time_from_ping() {
+    let uri    = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool   = ClientPool::new(uri, None);
+    let client = pool.pop();
+
+    let session = client.start_session(None).unwrap();
+    let reply = client.read_command_with_opts(
+        "admin",
+        &doc! { "ping": 1 },
+        None,
+        Some(&session.opts().unwrap())
+    ).unwrap();
+
+    // Standalone servers don't report these times
+    match reply.get_timestamp("operationTime") {
+        Ok(operation_time) => assert_eq!(Some(operation_time), session.operation_time()),
+        Err(_) => assert!(session.operation_time().is_none())
+    }
+    match reply.get_document("$clusterTime") {
+        Ok(cluster_time) => assert_eq!(Some(cluster_time.clone()), session.cluster_time()),
+        Err(_) => assert!(session.cluster_time().is_none())
+    }
+}
+
+#[test]
+fn test_command_with_session_is_rejected() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let database   = client.get_database("rust_driver_test");
+    let collection = database.get_collection("session_command");
+
+    let session = client.start_session(None).unwrap();
+    let mut options = CommandAndFindOptions::default();
+    options.session = Some(&session);
+
+    assert!(collection.command(doc! { "count": "session_command" }, Some(&options)).is_err());
+    assert!(database.command(doc! { "ping": 1 }, Some(&options)).is_err());
+    assert!(database.command_batch(doc! { "find": "session_command" }, Some(&options)).is_err());
+}
+
+#[test]
+fn test_read_your_writes_in_session() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "session");
+    collection.drop().unwrap_or(());
+
+    let session = client.start_session(None).unwrap();
+
+    let mut insert_options = InsertOptions::default();
+    insert_options.session = Some(&session);
+    collection.insert_one(&doc! { "key": "value" }, Some(&insert_options)).unwrap();
+    assert!(session.operation_time().is_some());
+
+    let mut find_options = CommandAndFindOptions::default();
+    find_options.session = Some(&session);
+    let documents: Vec<_> = collection
+        .find(&doc! { "key": "value" }, Some(&find_options))
+        .unwrap()
+        .map(|document| document.unwrap())
+        .collect();
+    assert_eq!(1, documents.len());
+    assert_eq!("value", documents[0].get_str("key").unwrap());
+}
+
+#[test]
+fn test_abort_transaction() {
+    let uri      = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool     = ClientPool::new(uri, None);
+    let client   = pool.pop();
+    let database = client.get_database("rust_driver_test");
+    let mut orders   = database.get_collection("transaction_orders");
+    let mut payments = database.get_collection("transaction_payments");
+    orders.drop().unwrap_or(());
+    payments.drop().unwrap_or(());
+    // Collections cannot be created inside a transaction on all server versions
+    database.create_collection("transaction_orders", None).unwrap();
+    database.create_collection("transaction_payments", None).unwrap();
+
+    let mut session = client.start_session(None).unwrap();
+    session.start_transaction(None).unwrap();
+    assert!(session.in_transaction());
+    assert!(session.start_transaction(None).is_err());
+
+    {
+        let mut insert_options = InsertOptions::default();
+        insert_options.session = Some(&session);
+        orders.insert_one(&doc! { "order": 1 }, Some(&insert_options)).unwrap();
+        payments.insert_one(&doc! { "order": 1 }, Some(&insert_options)).unwrap();
+    }
+
+    session.abort_transaction().unwrap();
+    assert!(!session.in_transaction());
+
+    assert_eq!(0, orders.count(&doc! {}, None).unwrap());
+    assert_eq!(0, payments.count(&doc! {}, None).unwrap());
+}
+
+#[test]
+fn test_commit_transaction() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let database   = client.get_database("rust_driver_test");
+    let mut collection = database.get_collection("transaction_commit");
+    collection.drop().unwrap_or(());
+    database.create_collection("transaction_commit", None).unwrap();
+
+    let mut transaction_options = TransactionOptions::default();
+    transaction_options.write_concern = Some(WriteConcern::new(WriteConcernLevel::Majority));
+
+    let mut session = client.start_session(None).unwrap();
+    session.start_transaction(Some(&transaction_options)).unwrap();
+
+    {
+        let mut insert_options = InsertOptions::default();
+        insert_options.session = Some(&session);
+        collection.insert_one(&doc! { "key": 1 }, Some(&insert_options)).unwrap();
+    }
+    assert_eq!(0, collection.count(&doc! {}, None).unwrap());
+
+    session.commit_transaction().unwrap();
+    assert_eq!(1, collection.count(&doc! {}, None).unwrap());
+}
+
+#[test]
+fn test_aggregate_in_transaction() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let database   = client.get_database("rust_driver_test");
+    let mut collection = database.get_collection("transaction_aggregate");
+    collection.drop().unwrap_or(());
+    database.create_collection("transaction_aggregate", None).unwrap();
+
+    let mut session = client.start_session(None).unwrap();
+    session.start_transaction(None).unwrap();
+
+    let pipeline = doc! {
+        "pipeline": [
+            { "$match": { "key": 1 } }
+        ]
+    };
+    {
+        let mut insert_options = InsertOptions::default();
+        insert_options.session = Some(&session);
+        collection.insert_one(&doc! { "key": 1 }, Some(&insert_options)).unwrap();
+
+        // The aggregate sees the uncommitted insert only when it runs in the transaction
+        let mut aggregate_options = AggregateOptions::default();
+        aggregate_options.session = Some(&session);
+        assert_eq!(1, collection.aggregate(&pipeline, Some(&aggregate_options)).unwrap().count());
+        assert_eq!(0, collection.aggregate(&pipeline, None).unwrap().count());
+    }
+
+    session.abort_transaction().unwrap();
+    assert_eq!(0, collection.aggregate(&pipeline, None).unwrap().count());
+}
+
+#[test]
+fn test_transient_transaction_error_label() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let database   = client.get_database("rust_driver_test");
+    let mut collection = database.get_collection("transaction_conflict");
+    collection.drop().unwrap_or(());
+    database.create_collection("transaction_conflict", None).unwrap();
+
+    let mut first_session = client.start_session(None).unwrap();
+    let mut second_session = client.start_session(None).unwrap();
+    first_session.start_transaction(None).unwrap();
+    second_session.start_transaction(None).unwrap();
+
+    // Inserting the same document in two transactions causes a write conflict
+    let error = {
+        let mut insert_options = InsertOptions::default();
+        insert_options.session = Some(&first_session);
+        collection.insert_one(&doc! { "_id": 1 }, Some(&insert_options)).unwrap();
+
+        insert_options.session = Some(&second_session);
+        collection.insert_one(&doc! { "_id": 1 }, Some(&insert_options)).unwrap_err()
+    };
+    assert!(error.has_error_label("TransientTransactionError"));
+    assert!(!error.has_error_label("UnknownTransactionCommitResult"));
+
+    second_session.abort_transaction().unwrap();
+    first_session.abort_transaction().unwrap();
+}
@@ -1,9 +1,113 @@
+extern crate bson;
 extern crate mongo_driver;
 
-use mongo_driver::write_concern::WriteConcern;
+mod helpers;
+
+use std::time::Duration;
+
+use bson::doc;
+
+use mongo_driver::client::{ClientPool,Uri};
+use mongo_driver::collection::InsertOptions;
+use mongo_driver::write_concern::{WriteConcern,WriteConcernLevel};
 
 #[test]
 fn test_default_write_concern() {
     let write_concern = WriteConcern::default();
     assert!(!write_concern.inner().is_null());
+    assert_eq!(WriteConcernLevel::Default, write_concern.level());
+}
+
+#[test]
+fn test_write_concern_levels() {
+    assert_eq!(
+        WriteConcernLevel::WriteUnacknowledged,
+        WriteConcern::new(WriteConcernLevel::WriteUnacknowledged).level()
+    );
+    assert_eq!(
+        WriteConcernLevel::Majority,
+        WriteConcern::new(WriteConcernLevel::Majority).level()
+    );
+    assert_eq!(
+        WriteConcernLevel::AtLeastNumberOfNodes(2),
+        WriteConcern::new(WriteConcernLevel::AtLeastNumberOfNodes(2)).level()
+    );
+}
+
+#[test]
+fn test_wtimeout() {
+    let mut write_concern = WriteConcern::new(WriteConcernLevel::Majority);
+    assert_eq!(Duration::from_millis(0), write_concern.wtimeout());
+
+    write_concern.set_wtimeout(Duration::from_millis(1500));
+    assert_eq!(Duration::from_millis(1500), write_concern.wtimeout());
+    assert_eq!(WriteConcernLevel::Majority, write_concern.level());
+
+    write_concern.set_wtimeout(Duration::from_millis(0));
+    assert_eq!(Duration::from_millis(0), write_concern.wtimeout());
+}
+
+#[test]
+fn test_journal() {
+    let write_concern = WriteConcern::new(WriteConcernLevel::Journal);
+    assert!(write_concern.journal());
+    assert_eq!(WriteConcernLevel::Journal, write_concern.level());
+
+    let mut write_concern = WriteConcern::new(WriteConcernLevel::Majority);
+    assert!(!write_concern.journal());
+    write_concern.set_journal(true).unwrap();
+    assert!(write_concern.journal());
+    write_concern.set_journal(false).unwrap();
+    assert!(!write_concern.journal());
+}
+
+#[test]
+fn test_journal_unacknowledged() {
+    let mut write_concern = WriteConcern::new(WriteConcernLevel::WriteUnacknowledged);
+    let result = write_concern.set_journal(true);
+    assert!(result.is_err());
+    assert!(format!("{:?}", result.err().unwrap()).contains("InvalidParamsError"));
+    assert!(!write_concern.journal());
+}
+
+#[test]
+fn test_write_concern_equality_and_debug() {
+    assert_eq!(WriteConcern::default(), WriteConcern::default());
+    assert_ne!(WriteConcern::default(), WriteConcern::new(WriteConcernLevel::Majority));
+
+    let mut write_concern = WriteConcern::new(WriteConcernLevel::Majority);
+    write_concern.set_wtimeout(Duration::from_millis(500));
+    assert_ne!(WriteConcern::new(WriteConcernLevel::Majority), write_concern);
+
+    // An explicit journal of false is sent to the server, so it differs from an unset journal
+    let mut explicit_journal = WriteConcern::new(WriteConcernLevel::Majority);
+    explicit_journal.set_journal(false).unwrap();
+    assert_ne!(WriteConcern::new(WriteConcernLevel::Majority), explicit_journal);
+
+    assert_eq!(
+        "WriteConcern { w: \"majority\", wtimeout: 500ms, journal: false }",
+        format!("{:?}", write_concern)
+    );
+}
+
+#[test]
+fn test_insert_with_write_concern_levels() {
+    let uri        = Uri::new(helpers::mongodb_test_connection_string()).unwrap();
+    let pool       = ClientPool::new(uri, None);
+    let client     = pool.pop();
+    let mut collection = client.get_collection("rust_driver_test", "write_concern");
+    collection.drop().unwrap_or(());
+
+    let levels = vec![
+        WriteConcernLevel::Default,
+        WriteConcernLevel::WriteUnacknowledged,
+        WriteConcernLevel::Majority,
+        WriteConcernLevel::AtLeastNumberOfNodes(1),
+        WriteConcernLevel::Journal
+    ];
+    for level in levels {
+        let mut options = InsertOptions::default();
+        options.write_concern = WriteConcern::new(level);
+        collection.insert_one(&doc!{"key": 1}, Some(&options)).expect("Could not insert");
+    }
 }